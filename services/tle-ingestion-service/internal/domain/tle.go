@@ -2,6 +2,7 @@ package domain
 
 import (
 	"context"
+	"time"
 
 	"github.com/SteeperMold/Orbitalik/tle-ingestion-service/internal/models"
 )
@@ -14,6 +15,8 @@ type TLEService interface {
 	GetAllTLEs(ctx context.Context) ([]*models.TLE, error)
 	GetTLEByNoradID(ctx context.Context, noradID int) (*models.TLE, error)
 	GetTLEBySatelliteName(ctx context.Context, name string) (*models.TLE, error)
+	GetTLEByNoradIDAt(ctx context.Context, noradID int, at time.Time, selection models.TLESelection) (*models.TLE, error)
+	GetTLEBySatelliteNameAt(ctx context.Context, name string, at time.Time, selection models.TLESelection) (*models.TLE, error)
 }
 
 type TLERepository interface {
@@ -21,4 +24,6 @@ type TLERepository interface {
 	GetAllTLEs(ctx context.Context) ([]*models.TLE, error)
 	GetTLEByNoradID(ctx context.Context, noradID int) (*models.TLE, error)
 	GetTLEBySatelliteName(ctx context.Context, name string) (*models.TLE, error)
+	GetTLEByNoradIDAt(ctx context.Context, noradID int, at time.Time, selection models.TLESelection) (*models.TLE, error)
+	GetTLEBySatelliteNameAt(ctx context.Context, name string, at time.Time, selection models.TLESelection) (*models.TLE, error)
 }
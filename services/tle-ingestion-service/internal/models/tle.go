@@ -11,3 +11,13 @@ type TLE struct {
 	Epoch         time.Time
 	FetchedAt     time.Time
 }
+
+// TLESelection picks one of the stored epochs of a satellite relative to a point in time.
+type TLESelection int
+
+const (
+	// SelectNearest picks the epoch closest to the requested time, before or after it.
+	SelectNearest TLESelection = iota
+	// SelectLatestBefore picks the latest epoch not after the requested time.
+	SelectLatestBefore
+)
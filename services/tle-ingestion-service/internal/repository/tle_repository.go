@@ -3,6 +3,8 @@ package repository
 import (
 	"context"
 	"errors"
+	"fmt"
+	"time"
 
 	"github.com/SteeperMold/Orbitalik/tle-ingestion-service/internal/domain"
 	"github.com/SteeperMold/Orbitalik/tle-ingestion-service/internal/models"
@@ -162,3 +164,49 @@ func (r *TLERepository) GetTLEBySatelliteName(ctx context.Context, name string)
 
 	return &tle, nil
 }
+
+func (r *TLERepository) GetTLEByNoradIDAt(ctx context.Context, noradID int, at time.Time, selection models.TLESelection) (*models.TLE, error) {
+	return r.getTLEAt(ctx, "norad_id", noradID, at, selection)
+}
+
+func (r *TLERepository) GetTLEBySatelliteNameAt(ctx context.Context, name string, at time.Time, selection models.TLESelection) (*models.TLE, error) {
+	return r.getTLEAt(ctx, "satellite_name", name, at, selection)
+}
+
+// getTLEAt picks one of the stored epochs of the satellite whose column equals value.
+// column is never user input.
+func (r *TLERepository) getTLEAt(ctx context.Context, column string, value any, at time.Time, selection models.TLESelection) (*models.TLE, error) {
+	var q string
+
+	switch selection {
+	case models.SelectLatestBefore:
+		q = fmt.Sprintf(`
+			SELECT id, norad_id, satellite_name, line1, line2, epoch, fetched_at
+			FROM tle
+			WHERE %s = $1 AND epoch <= $2
+			ORDER BY epoch DESC
+			LIMIT 1
+		`, column)
+	default:
+		q = fmt.Sprintf(`
+			SELECT id, norad_id, satellite_name, line1, line2, epoch, fetched_at
+			FROM tle
+			WHERE %s = $1
+			ORDER BY abs(extract(epoch FROM epoch - $2::timestamptz)), epoch DESC
+			LIMIT 1
+		`, column)
+	}
+
+	row := r.db.QueryRow(ctx, q, value, at)
+
+	var tle models.TLE
+	err := row.Scan(&tle.ID, &tle.NoradID, &tle.SatelliteName, &tle.Line1, &tle.Line2, &tle.Epoch, &tle.FetchedAt)
+	if err != nil {
+		if errors.Is(err, pgx.ErrNoRows) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	return &tle, nil
+}
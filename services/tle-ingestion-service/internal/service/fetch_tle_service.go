@@ -128,7 +128,7 @@ func parseTLE(name, line1, line2 string) (*models.TLE, error) {
 	}
 
 	// norad id = chars 2-7 of line1
-	noradID, err := strconv.Atoi(strings.TrimSpace(line1[2:7]))
+	noradID, err := parseCatalogNumber(line1[2:7])
 	if err != nil {
 		return nil, err
 	}
@@ -149,6 +149,34 @@ func parseTLE(name, line1, line2 string) (*models.TLE, error) {
 	}, nil
 }
 
+// alpha5Letters stand for 10 to 33 as the leading digit of Alpha-5 catalog numbers.
+const alpha5Letters = "ABCDEFGHJKLMNPQRSTUVWXYZ"
+
+// parseCatalogNumber reads a TLE catalog number, including the Alpha-5 scheme used
+// for numbers from 100000 to 339999.
+func parseCatalogNumber(field string) (int, error) {
+	field = strings.TrimSpace(field)
+	if field == "" {
+		return 0, fmt.Errorf("missing catalog number")
+	}
+
+	if field[0] >= '0' && field[0] <= '9' {
+		return strconv.Atoi(field)
+	}
+
+	leading := strings.IndexByte(alpha5Letters, field[0])
+	if leading < 0 {
+		return 0, fmt.Errorf("invalid Alpha-5 catalog number %q", field)
+	}
+
+	rest, err := strconv.Atoi(field[1:])
+	if err != nil {
+		return 0, fmt.Errorf("invalid Alpha-5 catalog number %q", field)
+	}
+
+	return (leading+10)*10000 + rest, nil
+}
+
 func parseEpoch(epochStr string) (time.Time, error) {
 	year, err := strconv.Atoi(epochStr[:2])
 	if err != nil {
@@ -2,6 +2,7 @@ package service
 
 import (
 	"context"
+	"time"
 
 	"github.com/SteeperMold/Orbitalik/tle-ingestion-service/internal/domain"
 	"github.com/SteeperMold/Orbitalik/tle-ingestion-service/internal/models"
@@ -28,3 +29,11 @@ func (s *TLEService) GetTLEByNoradID(ctx context.Context, noradID int) (*models.
 func (s *TLEService) GetTLEBySatelliteName(ctx context.Context, name string) (*models.TLE, error) {
 	return s.repository.GetTLEBySatelliteName(ctx, name)
 }
+
+func (s *TLEService) GetTLEByNoradIDAt(ctx context.Context, noradID int, at time.Time, selection models.TLESelection) (*models.TLE, error) {
+	return s.repository.GetTLEByNoradIDAt(ctx, noradID, at, selection)
+}
+
+func (s *TLEService) GetTLEBySatelliteNameAt(ctx context.Context, name string, at time.Time, selection models.TLESelection) (*models.TLE, error) {
+	return s.repository.GetTLEBySatelliteNameAt(ctx, name, at, selection)
+}
@@ -31,15 +31,39 @@ func (s *TLEServiceServer) GetTle(ctx context.Context, req *tlepb.GetTleRequest)
 	var err error
 	var identifier string
 
-	switch id := req.Identifier.Kind.(type) {
+	var selection models.TLESelection
+	switch req.Selection {
+	case tlepb.TleSelection_TLE_SELECTION_UNSPECIFIED, tlepb.TleSelection_TLE_SELECTION_NEAREST:
+		selection = models.SelectNearest
+	case tlepb.TleSelection_TLE_SELECTION_LATEST_BEFORE:
+		selection = models.SelectLatestBefore
+	default:
+		return nil, status.Error(codes.InvalidArgument, "unknown TLE selection")
+	}
+
+	if req.AtTime != nil {
+		if err := req.AtTime.CheckValid(); err != nil {
+			return nil, status.Error(codes.InvalidArgument, "invalid at_time")
+		}
+	}
+
+	switch id := req.GetIdentifier().GetKind().(type) {
 
 	case *tlepb.SatelliteIdentifier_NoradId:
 		identifier = fmt.Sprintf("norad_id=%d", id.NoradId)
-		tle, err = s.service.GetTLEByNoradID(ctx, int(id.NoradId))
+		if req.AtTime != nil {
+			tle, err = s.service.GetTLEByNoradIDAt(ctx, int(id.NoradId), req.AtTime.AsTime(), selection)
+		} else {
+			tle, err = s.service.GetTLEByNoradID(ctx, int(id.NoradId))
+		}
 
 	case *tlepb.SatelliteIdentifier_SatelliteName:
 		identifier = fmt.Sprintf("satellite_name=%s", id.SatelliteName)
-		tle, err = s.service.GetTLEBySatelliteName(ctx, id.SatelliteName)
+		if req.AtTime != nil {
+			tle, err = s.service.GetTLEBySatelliteNameAt(ctx, id.SatelliteName, req.AtTime.AsTime(), selection)
+		} else {
+			tle, err = s.service.GetTLEBySatelliteName(ctx, id.SatelliteName)
+		}
 
 	default:
 		return nil, status.Error(codes.InvalidArgument, "either norad_id or name must be set")
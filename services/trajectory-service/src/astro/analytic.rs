@@ -0,0 +1,322 @@
+use chrono::{DateTime, Utc};
+use uom::si::angle::radian;
+use uom::si::length::kilometer;
+
+use crate::astro::consts::{MU, TWO_PI};
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::elements::osculating_elements;
+use crate::astro::models::{GravityModel, PropagationMethod, PropagationModel};
+use crate::astro::propagator::{OrbitPropagator, StateVector, brouwer_orbit};
+use crate::astro::time::seconds_between;
+use crate::domain::errors::PropagationError;
+
+/// Kepler's equation is solved to this accuracy, in radians.
+const KEPLER_TOLERANCE: f64 = 1e-12;
+const KEPLER_MAX_ITERATIONS: usize = 50;
+
+/// Elliptic elements in km and radians.
+#[derive(Debug, Clone, Copy)]
+struct Keplerian {
+    semi_major_axis: f64,
+    eccentricity: f64,
+    inclination: f64,
+    right_ascension: f64,
+    argument_of_perigee: f64,
+    mean_anomaly: f64,
+}
+
+impl Keplerian {
+    /// Position and velocity of the orbit, in km and km/s.
+    ///
+    /// Vallado, *Fundamentals of Astrodynamics and Applications*, Algorithm 10 (COE2RV),
+    /// written with the eccentric anomaly so circular and equatorial orbits need no
+    /// special case.
+    fn state(&self) -> ([f64; 3], [f64; 3]) {
+        let a = self.semi_major_axis;
+        let e = self.eccentricity;
+        let eccentric_anomaly = eccentric_anomaly(self.mean_anomaly, e);
+        let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+        let sqrt_one_minus_e2 = (1.0 - e * e).sqrt();
+
+        // perifocal coordinates
+        let radius = a * (1.0 - e * cos_e);
+        let position = [a * (cos_e - e), a * sqrt_one_minus_e2 * sin_e];
+        let speed_factor = (MU * a).sqrt() / radius;
+        let velocity = [
+            -speed_factor * sin_e,
+            speed_factor * sqrt_one_minus_e2 * cos_e,
+        ];
+
+        // perifocal to inertial: the P (perigee) and Q axes
+        let (sin_raan, cos_raan) = self.right_ascension.sin_cos();
+        let (sin_argp, cos_argp) = self.argument_of_perigee.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let perigee_axis = [
+            cos_raan * cos_argp - sin_raan * sin_argp * cos_i,
+            sin_raan * cos_argp + cos_raan * sin_argp * cos_i,
+            sin_argp * sin_i,
+        ];
+        let q_axis = [
+            -cos_raan * sin_argp - sin_raan * cos_argp * cos_i,
+            -sin_raan * sin_argp + cos_raan * cos_argp * cos_i,
+            cos_argp * sin_i,
+        ];
+
+        (
+            std::array::from_fn(|i| position[0] * perigee_axis[i] + position[1] * q_axis[i]),
+            std::array::from_fn(|i| velocity[0] * perigee_axis[i] + velocity[1] * q_axis[i]),
+        )
+    }
+}
+
+/// Newton iterations on Kepler's equation M = E − e sin E.
+fn eccentric_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let m = mean_anomaly.rem_euclid(TWO_PI);
+    let mut e_anomaly = if eccentricity < 0.8 {
+        m
+    } else {
+        std::f64::consts::PI
+    };
+
+    for _ in 0..KEPLER_MAX_ITERATIONS {
+        let step = (e_anomaly - eccentricity * e_anomaly.sin() - m)
+            / (1.0 - eccentricity * e_anomaly.cos());
+        e_anomaly -= step;
+        if step.abs() < KEPLER_TOLERANCE {
+            break;
+        }
+    }
+
+    e_anomaly
+}
+
+const fn analytic_method(model: PropagationModel) -> PropagationMethod {
+    PropagationMethod {
+        model,
+        resonance: None,
+        gravity_model: Some(GravityModel::Wgs84),
+        afspc_compatibility_mode: false,
+    }
+}
+
+/// Unperturbed motion on the osculating orbit of the SGP4 state at the epoch.
+pub struct TwoBodyPropagator {
+    epoch: DateTime<Utc>,
+    orbit: Keplerian,
+    mean_motion: f64,
+}
+
+impl TwoBodyPropagator {
+    pub fn new(initial: &StateVector) -> Self {
+        let osculating = osculating_elements(
+            initial.epoch,
+            &Eci::from(initial.position),
+            &EciVelocity::from(initial.velocity),
+        );
+        let semi_major_axis = osculating.semi_major_axis.get::<kilometer>();
+
+        Self {
+            epoch: initial.epoch,
+            orbit: Keplerian {
+                semi_major_axis,
+                eccentricity: osculating.eccentricity,
+                inclination: osculating.inclination.get::<radian>(),
+                right_ascension: osculating.right_ascension.get::<radian>(),
+                argument_of_perigee: osculating.argument_of_perigee.get::<radian>(),
+                mean_anomaly: osculating.mean_anomaly.get::<radian>(),
+            },
+            mean_motion: (MU / semi_major_axis.powi(3)).sqrt(),
+        }
+    }
+}
+
+impl OrbitPropagator for TwoBodyPropagator {
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError> {
+        let dt = seconds_between(self.epoch, datetime);
+        let orbit = Keplerian {
+            mean_anomaly: self.orbit.mean_anomaly + self.mean_motion * dt,
+            ..self.orbit
+        };
+
+        let (position, velocity) = orbit.state();
+        Ok((Eci::from(position), EciVelocity::from(velocity)))
+    }
+
+    fn method(&self) -> PropagationMethod {
+        analytic_method(PropagationModel::TwoBody)
+    }
+}
+
+/// Brouwer mean elements with the first-order secular J2 rates of the node, argument
+/// of perigee and mean anomaly (Vallado, Section 9.6); the short- and long-periodic
+/// terms are left out, so positions are mean rather than osculating.
+pub struct J2SecularPropagator {
+    epoch: DateTime<Utc>,
+    orbit: Keplerian,
+    /// Secular rates in radians per second.
+    right_ascension_rate: f64,
+    argument_of_perigee_rate: f64,
+    mean_anomaly_rate: f64,
+}
+
+impl J2SecularPropagator {
+    pub fn new(elements: &sgp4::Elements) -> Result<Self, PropagationError> {
+        let geopotential = sgp4::WGS84;
+        let orbit = brouwer_orbit(elements)?;
+
+        // a = aₑ (kₑ / n)²ᐟ³, with n in radians per minute
+        let semi_major_axis =
+            geopotential.ae * (geopotential.ke / orbit.mean_motion).powf(2.0 / 3.0);
+        let mean_motion = orbit.mean_motion / 60.0;
+        let e = orbit.eccentricity;
+        let one_minus_e2 = 1.0 - e * e;
+        let cos_i = orbit.inclination.cos();
+
+        // 3/2 n J2 (R / p)²
+        let k = 1.5
+            * mean_motion
+            * geopotential.j2
+            * (geopotential.ae / (semi_major_axis * one_minus_e2)).powi(2);
+
+        Ok(Self {
+            epoch: elements.datetime.and_utc(),
+            orbit: Keplerian {
+                semi_major_axis,
+                eccentricity: e,
+                inclination: orbit.inclination,
+                right_ascension: orbit.right_ascension,
+                argument_of_perigee: orbit.argument_of_perigee,
+                mean_anomaly: orbit.mean_anomaly,
+            },
+            right_ascension_rate: -k * cos_i,
+            argument_of_perigee_rate: 0.5 * k * (5.0 * cos_i * cos_i - 1.0),
+            mean_anomaly_rate: mean_motion
+                + 0.5 * k * one_minus_e2.sqrt() * (3.0 * cos_i * cos_i - 1.0),
+        })
+    }
+}
+
+impl OrbitPropagator for J2SecularPropagator {
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError> {
+        let dt = seconds_between(self.epoch, datetime);
+        let orbit = Keplerian {
+            right_ascension: self.orbit.right_ascension + self.right_ascension_rate * dt,
+            argument_of_perigee: self.orbit.argument_of_perigee
+                + self.argument_of_perigee_rate * dt,
+            mean_anomaly: self.orbit.mean_anomaly + self.mean_anomaly_rate * dt,
+            ..self.orbit
+        };
+
+        let (position, velocity) = orbit.state();
+        Ok((Eci::from(position), EciVelocity::from(velocity)))
+    }
+
+    fn method(&self) -> PropagationMethod {
+        analytic_method(PropagationModel::J2Secular)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use uom::si::velocity::kilometer_per_second;
+
+    use crate::astro::propagator::Sgp4Propagator;
+
+    fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+        a.iter()
+            .zip(&b)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn components(position: &Eci, velocity: &EciVelocity) -> ([f64; 3], [f64; 3]) {
+        (
+            [position.x, position.y, position.z].map(|c| c.get::<kilometer>()),
+            [velocity.x, velocity.y, velocity.z].map(|c| c.get::<kilometer_per_second>()),
+        )
+    }
+
+    #[test]
+    fn two_body_returns_to_the_initial_state_after_a_period() {
+        let elements = sgp4::Elements::from_tle(
+            None,
+            b"1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992",
+            b"2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008",
+        )
+        .unwrap();
+        let initial = Sgp4Propagator::new(&elements)
+            .unwrap()
+            .epoch_state()
+            .unwrap();
+        let propagator = TwoBodyPropagator::new(&initial);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let period = TimeDelta::nanoseconds((TWO_PI / propagator.mean_motion * 1e9).round() as i64);
+
+        for datetime in [
+            initial.epoch,
+            initial.epoch + period,
+            initial.epoch - period,
+        ] {
+            let (position, velocity) = propagator.state_at(datetime).unwrap();
+            let (position, velocity) = components(&position, &velocity);
+
+            assert!(distance(position, initial.position) < 1e-6, "{datetime}");
+            assert!(distance(velocity, initial.velocity) < 1e-9, "{datetime}");
+        }
+    }
+
+    #[test]
+    fn two_body_starts_from_a_retrograde_equatorial_state() {
+        let initial = StateVector {
+            epoch: DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+            position: [0.0, 7000.0, 0.0],
+            velocity: [8.0, 0.0, 0.0],
+        };
+        let (position, velocity) = TwoBodyPropagator::new(&initial)
+            .state_at(initial.epoch)
+            .unwrap();
+        let (position, velocity) = components(&position, &velocity);
+
+        assert!(distance(position, initial.position) < 1e-6);
+        assert!(distance(velocity, initial.velocity) < 1e-9);
+    }
+
+    #[test]
+    fn j2_secular_node_of_a_sun_synchronous_orbit_follows_the_sun() {
+        // Landsat 8 class orbit: about 705 km high at 98.2°
+        let elements = sgp4::Elements {
+            object_name: None,
+            international_designator: None,
+            norad_id: 39084,
+            classification: sgp4::Classification::Unclassified,
+            datetime: DateTime::parse_from_rfc3339("2020-07-12T00:00:00Z")
+                .unwrap()
+                .naive_utc(),
+            mean_motion_dot: 0.0,
+            mean_motion_ddot: 0.0,
+            drag_term: 0.0,
+            element_set_number: 999,
+            inclination: 98.2,
+            right_ascension: 250.0,
+            eccentricity: 0.000_1,
+            argument_of_perigee: 90.0,
+            mean_anomaly: 270.0,
+            mean_motion: 14.571_1,
+            revolution_number: 0,
+            ephemeris_type: 0,
+        };
+        let propagator = J2SecularPropagator::new(&elements).unwrap();
+
+        let degrees_per_day = propagator.right_ascension_rate.to_degrees() * 86_400.0;
+        assert!(
+            (degrees_per_day - 0.9856).abs() < 0.005,
+            "node drifts by {degrees_per_day}°/day"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+
+use crate::astro;
+use crate::astro::coords::eci::Eci;
+use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::look_angles::{Horizon, LookAnglesComputation};
+use crate::astro::models::{CelestialBodyPosition, LookAngles};
+use crate::astro::moon::moon_eci;
+use crate::astro::position::PositionComputation;
+use crate::astro::sun::sun_eci;
+
+#[derive(Clone, Copy)]
+pub enum CelestialBody {
+    Sun,
+    Moon,
+}
+
+impl CelestialBody {
+    pub fn eci_at(self, datetime: DateTime<Utc>) -> Eci {
+        match self {
+            Self::Sun => sun_eci(datetime),
+            Self::Moon => moon_eci(datetime),
+        }
+    }
+
+    /// Topocentric horizon coordinates of the body as seen by `observer`.
+    ///
+    /// Earth orientation parameters are not applied: their effect is far below the
+    /// accuracy of the Sun and Moon models.
+    pub fn horizon_at(self, datetime: DateTime<Utc>, observer: &Geodetic) -> Horizon {
+        let gst = astro::time::utc_to_gst(datetime);
+        Horizon::observe(observer, self.eci_at(datetime).to_ecef(gst))
+    }
+
+    /// Position of the body at `datetime`, in the same frames as satellite positions.
+    ///
+    /// Look angles are topocentric, so they include the lunar parallax, and are only
+    /// computed when an observer is given.
+    pub fn position_at(
+        self,
+        datetime: DateTime<Utc>,
+        observer: Option<&Geodetic>,
+        position_compute: &PositionComputation,
+        look_angles_compute: &LookAnglesComputation,
+    ) -> CelestialBodyPosition {
+        let wants_look_angles = look_angles_compute.azimuth
+            || look_angles_compute.elevation
+            || look_angles_compute.range;
+        let observer = observer.filter(|_| wants_look_angles);
+
+        let eci = self.eci_at(datetime);
+
+        let ecef = (position_compute.ecef || observer.is_some())
+            .then(|| eci.to_ecef(astro::time::utc_to_gst(datetime)));
+
+        let look_angles = match (observer, &ecef) {
+            (Some(observer), Some(ecef)) => {
+                let horizon = Horizon::observe(observer, ecef.clone());
+                Some(LookAngles {
+                    azimuth: look_angles_compute.azimuth.then_some(horizon.azimuth),
+                    elevation: look_angles_compute.elevation.then_some(horizon.elevation),
+                    range: look_angles_compute.range.then_some(horizon.range),
+                    ..LookAngles::default()
+                })
+            }
+            _ => None,
+        };
+
+        CelestialBodyPosition {
+            eci: position_compute.eci.then_some(eci),
+            ecef: ecef.filter(|_| position_compute.ecef),
+            look_angles,
+        }
+    }
+}
@@ -1,11 +1,20 @@
 pub const A: f64 = 6378.137; // Equatorial radius in km
 pub const F: f64 = 1.0 / 298.257_223_563; // Flattening
 pub const E2: f64 = F * (2.0 - F); // Square of eccentricity
+pub const SPEED_OF_LIGHT: f64 = 299_792.458; // Speed of light in vacuum in km/s
+pub const MU: f64 = 398_600.5; // Gravitational parameter in km^3/s^2 (WGS84, as used by SGP4)
+pub const EARTH_ROTATION_RATE: f64 = 7.292_115_146_706_979e-5; // Earth angular velocity in rad/s
 
 // Julian day constants
 pub const JULIAN_DAY_OFFSET: f64 = 1524.5;
 pub const JULIAN_DAY_BASE: f64 = 2_451_545.0; // J2000 epoch
 pub const DAYS_PER_CENTURY: f64 = 36525.0;
+pub const MJD_OFFSET: f64 = 2_400_000.5; // JD of the Modified Julian Date origin
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+// Time scale offsets, in seconds
+pub const TT_MINUS_TAI: f64 = 32.184;
+pub const TAI_MINUS_GPS: f64 = 19.0;
 
 // Calendar conversion constants
 pub const JULIAN_YEAR_OFFSET: f64 = 4716.0;
@@ -21,3 +30,21 @@ pub const GMST_COEFF3: f64 = -6.2e-6;
 // Conversion
 pub const SECONDS_TO_DEGREES: f64 = 240.0;
 pub const TWO_PI: f64 = std::f64::consts::TAU;
+pub const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+// Solar system constants
+pub const AU: f64 = 149_597_870.7; // Astronomical unit in km
+pub const SUN_RADIUS: f64 = 696_000.0; // Mean solar radius in km
+pub const MU_SUN: f64 = 1.327_124_400_18e11; // Gravitational parameter of the Sun in km^3/s^2
+pub const MU_MOON: f64 = 4_902.800_066; // Gravitational parameter of the Moon in km^3/s^2
+
+// EGM96 geopotential, used by the numerical propagator
+pub const EGM96_MU: f64 = 398_600.441_5; // Gravitational parameter in km^3/s^2
+pub const EGM96_RADIUS: f64 = 6378.1363; // Equatorial radius in km
+pub const EGM96_ZONALS: [f64; 5] = [
+    1.082_626_680e-3,  // J2
+    -2.532_656_490e-6, // J3
+    -1.619_621_590e-6, // J4
+    -2.272_960_830e-7, // J5
+    5.406_812_390e-7,  // J6
+];
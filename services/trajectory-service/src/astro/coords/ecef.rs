@@ -1,18 +1,32 @@
 use std::ops::Sub;
 use uom::si::angle::radian;
-use uom::si::f64::Length;
+use uom::si::f64::{Angle, Length, Velocity};
 use uom::si::length::kilometer;
+use uom::si::velocity::kilometer_per_second;
 
 use crate::astro::consts::{A, E2};
+use crate::astro::coords::eci::Eci;
 use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::rotation::{self, Matrix3};
 
 /// Earth-Centered Earth-Fixed coordinates
+///
+/// PEF unless rotated into another
+/// [`EarthFixedFrame`](crate::astro::coords::frames::EarthFixedFrame).
+#[derive(Clone)]
 pub struct Ecef {
     pub x: Length,
     pub y: Length,
     pub z: Length,
 }
 
+/// Earth-Centered Earth-Fixed velocity
+pub struct EcefVelocity {
+    pub x: Velocity,
+    pub y: Velocity,
+    pub z: Velocity,
+}
+
 impl From<&Geodetic> for Ecef {
     fn from(geodetic: &Geodetic) -> Self {
         let alt_km = geodetic.alt.get::<kilometer>();
@@ -36,6 +50,58 @@ impl From<&Geodetic> for Ecef {
     }
 }
 
+impl Ecef {
+    /// Express the position in the frame reached by the passive rotation `m`.
+    pub fn rotated(&self, m: &Matrix3) -> Self {
+        let [x, y, z] = rotation::apply(
+            m,
+            [
+                self.x.get::<kilometer>(),
+                self.y.get::<kilometer>(),
+                self.z.get::<kilometer>(),
+            ],
+        );
+        Self {
+            x: Length::new::<kilometer>(x),
+            y: Length::new::<kilometer>(y),
+            z: Length::new::<kilometer>(z),
+        }
+    }
+
+    /// Inverse of [`Eci::to_ecef`]: rotate back by the Greenwich sidereal time.
+    pub fn to_eci(&self, gst: Angle) -> Eci {
+        let sin_gst = gst.sin();
+        let cos_gst = gst.cos();
+
+        Eci {
+            x: cos_gst.mul_add(self.x, -(sin_gst * self.y)),
+            y: sin_gst.mul_add(self.x, cos_gst * self.y),
+            z: self.z,
+        }
+    }
+}
+
+impl EcefVelocity {
+    /// Express the velocity in the frame reached by the passive rotation `m`.
+    ///
+    /// Only valid between Earth-fixed frames that do not rotate relative to each other.
+    pub fn rotated(&self, m: &Matrix3) -> Self {
+        let [x, y, z] = rotation::apply(
+            m,
+            [
+                self.x.get::<kilometer_per_second>(),
+                self.y.get::<kilometer_per_second>(),
+                self.z.get::<kilometer_per_second>(),
+            ],
+        );
+        Self {
+            x: Velocity::new::<kilometer_per_second>(x),
+            y: Velocity::new::<kilometer_per_second>(y),
+            z: Velocity::new::<kilometer_per_second>(z),
+        }
+    }
+}
+
 impl Sub for Ecef {
     type Output = Self;
 
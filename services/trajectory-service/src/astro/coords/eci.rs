@@ -1,9 +1,16 @@
-use uom::si::f64::{Angle, Length};
+use uom::si::angle::radian;
+use uom::si::f64::{Angle, Length, Velocity};
 use uom::si::length::kilometer;
+use uom::si::velocity::kilometer_per_second;
 
-use crate::astro::coords::ecef::Ecef;
+use crate::astro::consts::EARTH_ROTATION_RATE;
+use crate::astro::coords::ecef::{Ecef, EcefVelocity};
+use crate::astro::rotation::{self, Matrix3};
 
 /// Earth-Centered Inertial coordinates
+///
+/// TEME as produced by SGP4, unless rotated into another
+/// [`InertialFrame`](crate::astro::coords::frames::InertialFrame).
 #[derive(Clone)]
 pub struct Eci {
     pub x: Length,
@@ -11,6 +18,14 @@ pub struct Eci {
     pub z: Length,
 }
 
+/// Earth-Centered Inertial velocity
+#[derive(Clone)]
+pub struct EciVelocity {
+    pub x: Velocity,
+    pub y: Velocity,
+    pub z: Velocity,
+}
+
 impl From<[f64; 3]> for Eci {
     fn from(v: [f64; 3]) -> Self {
         Self {
@@ -21,7 +36,30 @@ impl From<[f64; 3]> for Eci {
     }
 }
 
+impl From<[f64; 3]> for EciVelocity {
+    fn from(v: [f64; 3]) -> Self {
+        Self {
+            x: Velocity::new::<kilometer_per_second>(v[0]),
+            y: Velocity::new::<kilometer_per_second>(v[1]),
+            z: Velocity::new::<kilometer_per_second>(v[2]),
+        }
+    }
+}
+
 impl Eci {
+    /// Express the position in the frame reached by the passive rotation `m`.
+    pub fn rotated(&self, m: &Matrix3) -> Self {
+        Self::from(rotation::apply(
+            m,
+            [
+                self.x.get::<kilometer>(),
+                self.y.get::<kilometer>(),
+                self.z.get::<kilometer>(),
+            ],
+        ))
+    }
+
+    /// Rotate the TEME position into PEF by the Greenwich sidereal time.
     pub fn to_ecef(&self, gst: Angle) -> Ecef {
         let sin_gst = gst.sin();
         let cos_gst = gst.cos();
@@ -37,3 +75,47 @@ impl Eci {
         }
     }
 }
+
+impl EciVelocity {
+    /// Express the velocity in the frame reached by the passive rotation `m`.
+    pub fn rotated(&self, m: &Matrix3) -> Self {
+        Self::from(rotation::apply(
+            m,
+            [
+                self.x.get::<kilometer_per_second>(),
+                self.y.get::<kilometer_per_second>(),
+                self.z.get::<kilometer_per_second>(),
+            ],
+        ))
+    }
+
+    /// Rotate the inertial velocity into the Earth-fixed frame.
+    ///
+    /// Besides the GST rotation, the Earth-fixed frame itself rotates with the Earth,
+    /// so the transport term `ω × r` (with `r` the Earth-fixed position) is subtracted:
+    ///
+    /// ```text
+    /// v_ecef = R(gst) · v_eci − ω × r_ecef
+    /// ```
+    pub fn to_ecef(&self, gst: Angle, position: &Ecef) -> EcefVelocity {
+        let sin_gst = gst.get::<radian>().sin();
+        let cos_gst = gst.get::<radian>().cos();
+
+        let vx = self.x.get::<kilometer_per_second>();
+        let vy = self.y.get::<kilometer_per_second>();
+        let vz = self.z.get::<kilometer_per_second>();
+
+        let x_km = position.x.get::<kilometer>();
+        let y_km = position.y.get::<kilometer>();
+
+        // ω × r = (-ω·y, ω·x, 0) for rotation about the z axis
+        let x_rate = cos_gst.mul_add(vx, sin_gst * vy) + EARTH_ROTATION_RATE * y_km;
+        let y_rate = (-sin_gst).mul_add(vx, cos_gst * vy) - EARTH_ROTATION_RATE * x_km;
+
+        EcefVelocity {
+            x: Velocity::new::<kilometer_per_second>(x_rate),
+            y: Velocity::new::<kilometer_per_second>(y_rate),
+            z: Velocity::new::<kilometer_per_second>(vz),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Reference frames of Cartesian positions and velocities.
+//!
+//! SGP4 produces states in TEME; every other frame is reached from there with
+//! the IAU-1976/1980 reduction (Vallado, *Fundamentals of Astrodynamics and
+//! Applications*, §3.7):
+//!
+//! ```text
+//! GCRS ←bias─ J2000 ←precession─ MOD ←nutation─ TOD ←equinoxes─ TEME ─GMST→ PEF ─polar motion→ ITRF
+//! ```
+
+use chrono::{DateTime, Utc};
+use uom::si::f64::Angle;
+
+use crate::astro::consts::ARCSEC_TO_RAD;
+use crate::astro::precession::teme_to_j2000;
+use crate::astro::rotation::{IDENTITY, Matrix3, multiply, rot_x, rot_y};
+
+/// Offsets of the GCRS pole and origin from the J2000 mean frame, in radians
+/// (IERS Conventions 2003, §5.5.1).
+const FRAME_BIAS_XI: f64 = -16.617e-3 * ARCSEC_TO_RAD;
+const FRAME_BIAS_ETA: f64 = -6.819_2e-3 * ARCSEC_TO_RAD;
+const FRAME_BIAS_ALPHA: f64 = -14.6e-3 * ARCSEC_TO_RAD;
+
+/// Non-rotating, Earth-centred frames.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum InertialFrame {
+    /// True equator, mean equinox of date, the frame of SGP4 output.
+    #[default]
+    Teme,
+    /// Mean equator and equinox of J2000.0 (EME2000).
+    J2000,
+    /// Geocentric Celestial Reference System, J2000 corrected for the frame bias.
+    Gcrs,
+}
+
+/// Frames rotating with the Earth.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum EarthFixedFrame {
+    /// Pseudo Earth-fixed: TEME rotated by Greenwich mean sidereal time.
+    #[default]
+    Pef,
+    /// International Terrestrial Reference Frame: PEF corrected for polar motion.
+    Itrf,
+}
+
+/// Position of the Celestial Intermediate Pole in the ITRF.
+///
+/// The default (no polar motion) makes the ITRF coincide with PEF.
+#[derive(Clone, Copy, Default)]
+pub struct PolarMotion {
+    pub xp: Angle,
+    pub yp: Angle,
+}
+
+impl InertialFrame {
+    /// Rotation from TEME into this frame at `datetime`.
+    ///
+    /// Inertial velocities are rotated with the same matrix: the rate of precession
+    /// and nutation is far below the accuracy of SGP4.
+    pub fn rotation_from_teme(self, datetime: DateTime<Utc>) -> Matrix3 {
+        match self {
+            Self::Teme => IDENTITY,
+            Self::J2000 => teme_to_j2000(datetime),
+            Self::Gcrs => multiply(&j2000_to_gcrs(), &teme_to_j2000(datetime)),
+        }
+    }
+}
+
+impl EarthFixedFrame {
+    /// Rotation from PEF into this frame.
+    pub fn rotation_from_pef(self, polar_motion: PolarMotion) -> Matrix3 {
+        match self {
+            Self::Pef => IDENTITY,
+            Self::Itrf => multiply(
+                &rot_y(-polar_motion.xp.value),
+                &rot_x(-polar_motion.yp.value),
+            ),
+        }
+    }
+}
+
+/// Frame bias from the J2000 mean frame to the GCRS, to first order in the offsets.
+fn j2000_to_gcrs() -> Matrix3 {
+    [
+        [1.0, -FRAME_BIAS_ALPHA, FRAME_BIAS_XI],
+        [FRAME_BIAS_ALPHA, 1.0, FRAME_BIAS_ETA],
+        [-FRAME_BIAS_XI, -FRAME_BIAS_ETA, 1.0],
+    ]
+}
@@ -1,3 +1,4 @@
 pub mod ecef;
 pub mod eci;
+pub mod frames;
 pub mod geodetic;
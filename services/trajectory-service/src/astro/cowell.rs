@@ -0,0 +1,535 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+use uom::si::length::kilometer;
+
+use crate::astro::consts::{
+    EARTH_ROTATION_RATE, EGM96_MU, EGM96_RADIUS, EGM96_ZONALS, MU_MOON, MU_SUN, SECONDS_PER_DAY,
+};
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::models::{GravityModel, PropagationMethod, PropagationModel};
+use crate::astro::moon::moon_eci;
+use crate::astro::propagator::{OrbitPropagator, StateVector};
+use crate::astro::sun::sun_eci;
+use crate::astro::time::{add_seconds, seconds_between};
+use crate::domain::errors::PropagationError;
+
+/// Longest span from the epoch the integrator runs over; the model is meant for
+/// short-term work and its cost grows with the span.
+const MAX_SPAN_DAYS: f64 = 30.0;
+
+/// Below this altitude, in km, the satellite is considered to have re-entered.
+const REENTRY_ALTITUDE: f64 = 100.0;
+
+/// Step control of the integrator, applied to km and km/s components alike.
+const RELATIVE_TOLERANCE: f64 = 1e-11;
+const ABSOLUTE_TOLERANCE: f64 = 1e-11;
+const INITIAL_STEP: f64 = 30.0;
+const MAX_STEP: f64 = 900.0;
+const MIN_STEP: f64 = 1e-6;
+
+/// One accepted step per interval of this many nanoseconds is kept to restart later
+/// propagations from, at most a few thousand over the whole span.
+const CHECKPOINT_INTERVAL: i64 = 3_600_000_000_000;
+
+/// ρ₀ (kg/m³) in SGP4's B* definition, times the Earth radius: B* = ρ₀ R Cd A / 2m.
+const BSTAR_REFERENCE_DENSITY: f64 = 0.156_966_15;
+
+/// Exponential atmosphere: base altitude (km), density at it (kg/m³) and scale height
+/// (km). Vallado, *Fundamentals of Astrodynamics and Applications*, Table 8-4.
+const ATMOSPHERE: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Dormand–Prince 5(4) coefficients.
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0; 6],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+/// Fifth-order weights (the last row of `A`, the seventh stage being the new state).
+const B5: [f64; 7] = [
+    35.0 / 384.0,
+    0.0,
+    500.0 / 1113.0,
+    125.0 / 192.0,
+    -2187.0 / 6784.0,
+    11.0 / 84.0,
+    0.0,
+];
+const B4: [f64; 7] = [
+    5179.0 / 57600.0,
+    0.0,
+    7571.0 / 16695.0,
+    393.0 / 640.0,
+    -92097.0 / 339_200.0,
+    187.0 / 2100.0,
+    1.0 / 40.0,
+];
+
+/// Position (km) and velocity (km/s).
+type State = [f64; 6];
+
+/// Numerical integration of the equations of motion in TEME from the SGP4 state at the
+/// epoch, with an adaptive Dormand–Prince 5(4) integrator.
+///
+/// Forces are the EGM96 zonal harmonics J2–J6, drag in an exponential atmosphere
+/// rotating with the Earth, with the ballistic coefficient derived from B*, and the
+/// Sun and Moon as point masses. The frame is treated as inertial over the span.
+/// An accepted step per hour is kept, so later calls resume from the closest state on
+/// the same side of the epoch instead of integrating from the epoch again; propagators
+/// are shared through the propagator cache for this to outlive a request.
+pub struct CowellPropagator {
+    epoch: DateTime<Utc>,
+    /// Cd·A/m in m²/kg.
+    ballistic_coefficient: f64,
+    /// States by nanoseconds from the epoch.
+    checkpoints: Mutex<BTreeMap<i64, State>>,
+}
+
+impl CowellPropagator {
+    pub fn new(initial: &StateVector, bstar: f64) -> Self {
+        let [x, y, z] = initial.position;
+        let [vx, vy, vz] = initial.velocity;
+
+        Self {
+            epoch: initial.epoch,
+            // a negative B* fits unmodelled effects rather than drag
+            ballistic_coefficient: (2.0 * bstar / BSTAR_REFERENCE_DENSITY).max(0.0),
+            checkpoints: Mutex::new(BTreeMap::from([(0, [x, y, z, vx, vy, vz])])),
+        }
+    }
+
+    /// Closest recorded state between the epoch and `target` (in nanoseconds).
+    fn start_for(&self, target: i64) -> (i64, State) {
+        let checkpoints = self
+            .checkpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let closest = if target >= 0 {
+            checkpoints.range(0..=target).next_back()
+        } else {
+            checkpoints.range(target..=0).next()
+        };
+
+        // the epoch state is always recorded
+        closest.map_or((0, checkpoints[&0]), |(&t, &state)| (t, state))
+    }
+
+    fn record(&self, states: Vec<(i64, State)>) {
+        let mut checkpoints = self
+            .checkpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for (t, state) in states {
+            let interval = t.div_euclid(CHECKPOINT_INTERVAL) * CHECKPOINT_INTERVAL;
+            if checkpoints
+                .range(interval..interval + CHECKPOINT_INTERVAL)
+                .next()
+                .is_none()
+            {
+                checkpoints.insert(t, state);
+            }
+        }
+    }
+
+    /// Integrate from `start` (at `t0` seconds from the epoch) to `t1`, returning the
+    /// final state and the accepted steps.
+    #[allow(clippy::cast_possible_truncation)]
+    fn integrate(
+        &self,
+        t0: f64,
+        start: State,
+        t1: f64,
+    ) -> Result<(State, Vec<(i64, State)>), PropagationError> {
+        let direction = (t1 - t0).signum();
+        let mut t = t0;
+        let mut state = start;
+        let mut step = INITIAL_STEP;
+        let mut accepted = Vec::new();
+
+        loop {
+            let remaining = (t1 - t) * direction;
+            let last = step >= remaining;
+            let h = if last { t1 - t } else { step * direction };
+            let (candidate, error) = self.dormand_prince_step(t, &state, h);
+
+            if !error.is_finite() {
+                return Err(integration_failed(t));
+            }
+
+            let accept = error <= 1.0;
+            if accept {
+                // the last step lands on `t1` exactly, however short it is
+                t = if last { t1 } else { t + h };
+                state = candidate;
+                check_altitude(t, &state)?;
+                accepted.push(((t * 1e9).round() as i64, state));
+
+                if last {
+                    break;
+                }
+            }
+
+            // standard controller, with the growth and shrink factors bounded
+            let factor = if error == 0.0 {
+                5.0
+            } else {
+                (0.9 * error.powf(-0.2)).clamp(0.2, 5.0)
+            };
+            step = (h.abs() * factor).min(MAX_STEP);
+            if !accept && step < MIN_STEP {
+                return Err(integration_failed(t));
+            }
+        }
+
+        Ok((state, accepted))
+    }
+
+    /// One step of size `h`, with the scaled norm of the embedded error estimate.
+    fn dormand_prince_step(&self, t: f64, state: &State, h: f64) -> (State, f64) {
+        let mut k = [[0.0; 6]; 7];
+
+        for stage in 0..7 {
+            let stage_state: State = std::array::from_fn(|i| {
+                state[i] + h * (0..stage).map(|j| A[stage][j] * k[j][i]).sum::<f64>()
+            });
+            k[stage] = self.derivative(t + C[stage] * h, &stage_state);
+        }
+
+        let next: State =
+            std::array::from_fn(|i| state[i] + h * (0..7).map(|j| B5[j] * k[j][i]).sum::<f64>());
+
+        let error = (0..6)
+            .map(|i| {
+                let estimate = h * (0..7).map(|j| (B5[j] - B4[j]) * k[j][i]).sum::<f64>();
+                let scale =
+                    ABSOLUTE_TOLERANCE + RELATIVE_TOLERANCE * state[i].abs().max(next[i].abs());
+                (estimate / scale).abs()
+            })
+            .fold(0.0, f64::max);
+
+        (next, error)
+    }
+
+    fn derivative(&self, t: f64, state: &State) -> State {
+        let r = [state[0], state[1], state[2]];
+        let v = [state[3], state[4], state[5]];
+
+        let gravity = geopotential_acceleration(r);
+        let drag = self.drag_acceleration(r, v);
+        let third_body = third_body_acceleration(self.datetime(t), r);
+
+        [
+            v[0],
+            v[1],
+            v[2],
+            gravity[0] + drag[0] + third_body[0],
+            gravity[1] + drag[1] + third_body[1],
+            gravity[2] + drag[2] + third_body[2],
+        ]
+    }
+
+    /// −½ ρ (Cd A/m) |v − ω × r| (v − ω × r), with the atmosphere co-rotating with the Earth.
+    fn drag_acceleration(&self, r: [f64; 3], v: [f64; 3]) -> [f64; 3] {
+        if self.ballistic_coefficient == 0.0 {
+            return [0.0; 3];
+        }
+
+        let density = atmospheric_density(norm(r) - EGM96_RADIUS);
+        let v_rel = [
+            v[0] + EARTH_ROTATION_RATE * r[1],
+            v[1] - EARTH_ROTATION_RATE * r[0],
+            v[2],
+        ];
+        let speed = norm(v_rel);
+
+        // kg/m³ · m²/kg · (km/s)² gives 1e3 km/s² per unit
+        let factor = -0.5 * density * self.ballistic_coefficient * speed * 1e3;
+        v_rel.map(|c| factor * c)
+    }
+
+    fn datetime(&self, t: f64) -> DateTime<Utc> {
+        add_seconds(self.epoch, t)
+    }
+}
+
+impl OrbitPropagator for CowellPropagator {
+    #[allow(clippy::cast_precision_loss)]
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError> {
+        let target = seconds_between(self.epoch, datetime);
+        if target.abs() > MAX_SPAN_DAYS * SECONDS_PER_DAY {
+            return Err(PropagationError::IntegrationSpanExceeded {
+                days_since_epoch: target / SECONDS_PER_DAY,
+                max_days: MAX_SPAN_DAYS,
+            });
+        }
+
+        let target_nanos = (datetime - self.epoch)
+            .num_nanoseconds()
+            .unwrap_or_default();
+        let (start, start_state) = self.start_for(target_nanos);
+
+        let state = if start == target_nanos {
+            start_state
+        } else {
+            let (state, accepted) = self.integrate(start as f64 * 1e-9, start_state, target)?;
+            self.record(accepted);
+            state
+        };
+
+        Ok((
+            Eci::from([state[0], state[1], state[2]]),
+            EciVelocity::from([state[3], state[4], state[5]]),
+        ))
+    }
+
+    fn method(&self) -> PropagationMethod {
+        PropagationMethod {
+            model: PropagationModel::Cowell,
+            resonance: None,
+            gravity_model: Some(GravityModel::Egm96),
+            afspc_compatibility_mode: false,
+        }
+    }
+}
+
+/// Central body and the EGM96 zonal harmonics J2–J6.
+fn geopotential_acceleration(r: [f64; 3]) -> [f64; 3] {
+    zonal_acceleration(r, &EGM96_ZONALS)
+}
+
+/// Central body and the zonal harmonics `zonals`, starting from J2:
+/// aₙ = μ Jₙ Rⁿ / rⁿ⁺² [((n+1) Pₙ(s) + s Pₙ'(s)) r̂ − Pₙ'(s) ẑ], with s = z / r.
+#[allow(clippy::cast_precision_loss)]
+fn zonal_acceleration(r: [f64; 3], zonals: &[f64]) -> [f64; 3] {
+    let radius = norm(r);
+    let unit = r.map(|c| c / radius);
+    let s = unit[2];
+
+    let central = -EGM96_MU / (radius * radius);
+    let mut radial = central;
+    let mut polar = 0.0;
+
+    // Legendre polynomials and their derivatives, from P₀ and P₁
+    let (mut p_prev, mut p) = (1.0, s);
+    let (mut dp_prev, mut dp) = (0.0, 1.0);
+    let ratio = EGM96_RADIUS / radius;
+    let mut ratio_n = ratio;
+
+    for (index, jn) in zonals.iter().enumerate() {
+        let n = (index + 1) as f64;
+
+        // raise P and P' from degree n to n + 1
+        let p_next = ((2.0 * n + 1.0) * s * p - n * p_prev) / (n + 1.0);
+        let dp_next = dp_prev + (2.0 * n + 1.0) * p;
+        (p_prev, p) = (p, p_next);
+        (dp_prev, dp) = (dp, dp_next);
+        ratio_n *= ratio;
+
+        let degree = n + 1.0;
+        let scale = -central * jn * ratio_n;
+        radial += scale * ((degree + 1.0) * p + s * dp);
+        polar -= scale * dp;
+    }
+
+    [radial * unit[0], radial * unit[1], radial * unit[2] + polar]
+}
+
+/// Sun and Moon as point masses, relative to the Earth's centre.
+fn third_body_acceleration(datetime: DateTime<Utc>, r: [f64; 3]) -> [f64; 3] {
+    let mut acceleration = [0.0; 3];
+
+    for (mu, body) in [(MU_SUN, sun_eci(datetime)), (MU_MOON, moon_eci(datetime))] {
+        let body = [
+            body.x.get::<kilometer>(),
+            body.y.get::<kilometer>(),
+            body.z.get::<kilometer>(),
+        ];
+        let relative: [f64; 3] = std::array::from_fn(|i| body[i] - r[i]);
+        let relative_cubed = norm(relative).powi(3);
+        let body_cubed = norm(body).powi(3);
+
+        for i in 0..3 {
+            acceleration[i] += mu * (relative[i] / relative_cubed - body[i] / body_cubed);
+        }
+    }
+
+    acceleration
+}
+
+/// Density in kg/m³ at `altitude` km, from the layer below it.
+fn atmospheric_density(altitude: f64) -> f64 {
+    let (base, density, scale_height) = ATMOSPHERE
+        .iter()
+        .rev()
+        .find(|(base, ..)| altitude >= *base)
+        .copied()
+        .unwrap_or(ATMOSPHERE[0]);
+
+    density * (-(altitude - base) / scale_height).exp()
+}
+
+fn check_altitude(t: f64, state: &State) -> Result<(), PropagationError> {
+    if norm([state[0], state[1], state[2]]) - EGM96_RADIUS < REENTRY_ALTITUDE {
+        return Err(PropagationError::Reentered {
+            minutes_since_epoch: t / 60.0,
+        });
+    }
+    Ok(())
+}
+
+const fn integration_failed(t: f64) -> PropagationError {
+    PropagationError::IntegrationFailed {
+        minutes_since_epoch: t / 60.0,
+    }
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    use crate::astro::propagator::Sgp4Propagator;
+
+    const ISS_LINE1: &str = "1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992";
+    const ISS_LINE2: &str = "2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008";
+
+    fn iss_state() -> StateVector {
+        let elements =
+            sgp4::Elements::from_tle(None, ISS_LINE1.as_bytes(), ISS_LINE2.as_bytes()).unwrap();
+
+        Sgp4Propagator::new(&elements)
+            .unwrap()
+            .epoch_state()
+            .unwrap()
+    }
+
+    #[test]
+    fn j2_acceleration_matches_the_closed_form() {
+        let j2 = EGM96_ZONALS[0];
+
+        for r in [
+            [7000.0, 0.0, 0.0],
+            [-3000.0, 4000.0, 5000.0],
+            [1000.0, -2000.0, -6800.0],
+        ] {
+            let radius = norm(r);
+            let z2 = (r[2] / radius).powi(2);
+            let factor = 1.5 * j2 * (EGM96_RADIUS / radius).powi(2);
+            let central = -EGM96_MU / radius.powi(3);
+            let expected = [
+                central * r[0] * factor.mul_add(1.0 - 5.0 * z2, 1.0),
+                central * r[1] * factor.mul_add(1.0 - 5.0 * z2, 1.0),
+                central * r[2] * factor.mul_add(3.0 - 5.0 * z2, 1.0),
+            ];
+
+            let acceleration = zonal_acceleration(r, &EGM96_ZONALS[..1]);
+            for (axis, (actual, expected)) in acceleration.iter().zip(expected).enumerate() {
+                assert!(
+                    (actual - expected).abs() < 1e-15,
+                    "axis {axis} at {r:?}: {actual} km/s², expected {expected} km/s²"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stays_close_to_sgp4_over_an_orbit_without_drag() {
+        let initial = iss_state();
+        let propagator = CowellPropagator::new(&initial, 0.0);
+        let sgp4 = Sgp4Propagator::new(
+            &sgp4::Elements::from_tle(None, ISS_LINE1.as_bytes(), ISS_LINE2.as_bytes()).unwrap(),
+        )
+        .unwrap();
+
+        for minutes in [30, 60, 93] {
+            let datetime = initial.epoch + TimeDelta::minutes(minutes);
+            let (position, _) = propagator.state_at(datetime).unwrap();
+            let (expected, _) = sgp4.state_at(datetime).unwrap();
+
+            let distance = norm([
+                (position.x - expected.x).get::<kilometer>(),
+                (position.y - expected.y).get::<kilometer>(),
+                (position.z - expected.z).get::<kilometer>(),
+            ]);
+            assert!(
+                distance < 2.0,
+                "{distance} km from SGP4 after {minutes} min"
+            );
+        }
+    }
+
+    #[test]
+    fn lands_on_targets_closer_than_the_minimum_step() {
+        let initial = iss_state();
+        let propagator = CowellPropagator::new(&initial, 0.0);
+
+        for offset in [
+            TimeDelta::nanoseconds(100),
+            TimeDelta::seconds(30) + TimeDelta::nanoseconds(100),
+        ] {
+            assert!(propagator.state_at(initial.epoch + offset).is_ok());
+            assert!(propagator.state_at(initial.epoch - offset).is_ok());
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+
+use crate::astro::models::Eclipse;
+use crate::astro::propagator::Propagator;
+use crate::astro::search::negative_intervals;
+use crate::astro::time::{add_seconds, seconds_between};
+use crate::domain::errors::PropagationError;
+
+/// Step of the coarse shadow scan, in seconds.
+///
+/// Umbra phases shorter than this (only possible on grazing geometries) may be missed.
+const SCAN_STEP_SECONDS: f64 = 30.0;
+
+/// Resolution of the refined shadow entry and exit instants, in seconds.
+const TIME_TOLERANCE_SECONDS: f64 = 1e-3;
+
+impl Propagator {
+    /// Find the eclipses of the satellite by Earth's shadow between `start` and `end`.
+    ///
+    /// Penumbra and umbra boundaries are located independently with the conical
+    /// shadow model; every umbra phase is attached to the penumbra phase around it.
+    /// Eclipses already in progress at `start` (or still in progress at `end`)
+    /// are clipped to the window.
+    pub fn eclipses_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Eclipse>, PropagationError> {
+        let duration = seconds_between(start, end);
+
+        let penumbra = |t: f64| Ok(self.shadow_at(add_seconds(start, t))?.penumbra_margin());
+        let umbra = |t: f64| Ok(self.shadow_at(add_seconds(start, t))?.umbra_margin());
+
+        let samples = std::iter::successors(Some(0.0), |t| Some(t + SCAN_STEP_SECONDS))
+            .take_while(|&t| t < duration)
+            .chain(std::iter::once(duration))
+            .map(|t| {
+                self.shadow_at(add_seconds(start, t))
+                    .map(|shadow| (t, shadow))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let penumbra_samples = samples
+            .iter()
+            .map(|(t, shadow)| (*t, shadow.penumbra_margin()))
+            .collect::<Vec<_>>();
+        let umbra_samples = samples
+            .iter()
+            .map(|(t, shadow)| (*t, shadow.umbra_margin()))
+            .collect::<Vec<_>>();
+
+        let penumbra_intervals =
+            negative_intervals(&penumbra_samples, penumbra, TIME_TOLERANCE_SECONDS)?;
+        let umbra_intervals = negative_intervals(&umbra_samples, umbra, TIME_TOLERANCE_SECONDS)?;
+
+        Ok(penumbra_intervals
+            .into_iter()
+            .map(|(entry, exit)| {
+                let umbra = umbra_intervals
+                    .iter()
+                    .find(|&&(umbra_entry, umbra_exit)| umbra_entry >= entry && umbra_exit <= exit);
+
+                Eclipse {
+                    penumbra_entry: add_seconds(start, entry),
+                    umbra_entry: umbra.map(|&(umbra_entry, _)| add_seconds(start, umbra_entry)),
+                    umbra_exit: umbra.map(|&(_, umbra_exit)| add_seconds(start, umbra_exit)),
+                    penumbra_exit: add_seconds(start, exit),
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,447 @@
+//! Mean element sets, independent of the format they are exchanged in.
+//!
+//! Element sets come as two-line element sets (TLE) or as CCSDS Orbit Mean-Elements
+//! Messages (OMM, see [`crate::astro::omm`]). Both carry the same SGP4 mean elements,
+//! but the fixed-width TLE cannot hold catalog numbers above 99999: these use the
+//! Alpha-5 scheme, where the first of the five digits is replaced by a letter
+//! (A = 10 … Z = 33, skipping I and O), covering numbers up to 339999.
+//!
+//! See: [CelesTrak GP data formats](https://celestrak.org/NORAD/documentation/gp-data-formats.php)
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+
+use crate::domain::errors::ElementSetError;
+
+/// Letters of the Alpha-5 scheme, standing for 10 to 33.
+const ALPHA5_LETTERS: &[u8; 24] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Largest catalog number a TLE can hold.
+const MAX_TLE_NORAD_ID: u32 = 339_999;
+
+const TLE_LINE_LENGTH: usize = 69;
+
+/// SGP4 mean elements of a satellite, referred to the TEME frame of their epoch.
+///
+/// Angles are in degrees and mean motions in revolutions per day, as in both formats.
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Clone)]
+pub struct ElementSet {
+    pub object_name: Option<String>,
+    /// International designator, e.g. `1998-067A`.
+    pub object_id: Option<String>,
+    pub norad_id: u32,
+    pub classification: sgp4::Classification,
+    pub epoch: DateTime<Utc>,
+    /// Kozai mean motion.
+    pub mean_motion: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub right_ascension: f64,
+    pub argument_of_perigee: f64,
+    pub mean_anomaly: f64,
+    pub ephemeris_type: u8,
+    pub element_set_number: u64,
+    pub revolution_number: u64,
+    /// Drag term, in inverse Earth radii.
+    pub bstar: f64,
+    /// First time derivative of the mean motion divided by two, in rev/day².
+    pub mean_motion_dot: f64,
+    /// Second time derivative of the mean motion divided by six, in rev/day³.
+    pub mean_motion_ddot: f64,
+}
+
+impl ElementSet {
+    /// Parse a TLE, accepting Alpha-5 catalog numbers.
+    pub fn from_tle(
+        object_name: Option<String>,
+        line1: &str,
+        line2: &str,
+    ) -> Result<Self, ElementSetError> {
+        let line1 = line1.trim_end().as_bytes();
+        let line2 = line2.trim_end().as_bytes();
+
+        // the sgp4 parser only reads numeric catalog numbers, so an Alpha-5 one is
+        // decoded here and replaced by zeros (with fixed checksums) before parsing
+        let alpha5 = match (line1.get(2..7), line2.get(2..7)) {
+            (Some(id1), Some(id2)) if id1[0].is_ascii_alphabetic() => {
+                if id1 != id2 {
+                    return Err(ElementSetError::TleFormat(
+                        "catalog numbers of the two lines differ",
+                    ));
+                }
+                Some(decode_alpha5(id1)?)
+            }
+            _ => None,
+        };
+
+        let elements = match alpha5 {
+            Some(_) => sgp4::Elements::from_tle(
+                object_name,
+                &without_catalog_number(line1)?,
+                &without_catalog_number(line2)?,
+            )?,
+            None => sgp4::Elements::from_tle(object_name, line1, line2)?,
+        };
+
+        let mut element_set = Self::try_from(elements)?;
+        if let Some(norad_id) = alpha5 {
+            element_set.norad_id = norad_id;
+        }
+
+        Ok(element_set)
+    }
+
+    /// Format as the two lines of a TLE, using Alpha-5 above catalog number 99999.
+    pub fn to_tle(&self) -> Result<(String, String), ElementSetError> {
+        let catalog_number = encode_alpha5(self.norad_id)?;
+
+        let line1 = format!(
+            "1 {catalog_number}{} {:<8} {} {} {} {} {} {:>4}",
+            self.classification_code(),
+            tle_designator(self.object_id.as_deref()),
+            tle_epoch(self.epoch)?,
+            tle_mean_motion_dot(self.mean_motion_dot)?,
+            tle_exponential(self.mean_motion_ddot, "MEAN_MOTION_DDOT")?,
+            tle_exponential(self.bstar, "BSTAR")?,
+            self.ephemeris_type % 10,
+            self.element_set_number % 10_000,
+        );
+
+        if !(0.0..=180.0).contains(&self.inclination) {
+            return Err(ElementSetError::NotRepresentable("INCLINATION"));
+        }
+        if !(0.0..1.0).contains(&self.eccentricity) {
+            return Err(ElementSetError::NotRepresentable("ECCENTRICITY"));
+        }
+        if !(0.0..100.0).contains(&self.mean_motion) {
+            return Err(ElementSetError::NotRepresentable("MEAN_MOTION"));
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let eccentricity = (self.eccentricity * 1e7).round().min(9_999_999.0) as u32;
+
+        let line2 = format!(
+            "2 {catalog_number} {:8.4} {:8.4} {eccentricity:07} {:8.4} {:8.4} {:11.8}{:>5}",
+            self.inclination,
+            self.right_ascension.rem_euclid(360.0),
+            self.argument_of_perigee.rem_euclid(360.0),
+            self.mean_anomaly.rem_euclid(360.0),
+            self.mean_motion,
+            self.revolution_number % 100_000,
+        );
+
+        Ok((with_checksum(&line1), with_checksum(&line2)))
+    }
+}
+
+impl ElementSet {
+    /// One-letter classification used by both formats.
+    pub const fn classification_code(&self) -> char {
+        match self.classification {
+            sgp4::Classification::Unclassified => 'U',
+            sgp4::Classification::Classified => 'C',
+            sgp4::Classification::Secret => 'S',
+        }
+    }
+}
+
+impl TryFrom<sgp4::Elements> for ElementSet {
+    type Error = ElementSetError;
+
+    fn try_from(elements: sgp4::Elements) -> Result<Self, Self::Error> {
+        let norad_id = u32::try_from(elements.norad_id).map_err(|_| {
+            ElementSetError::InvalidField("NORAD_CAT_ID", elements.norad_id.to_string())
+        })?;
+
+        Ok(Self {
+            object_name: elements.object_name,
+            object_id: elements.international_designator,
+            norad_id,
+            classification: elements.classification,
+            epoch: elements.datetime.and_utc(),
+            mean_motion: elements.mean_motion,
+            eccentricity: elements.eccentricity,
+            inclination: elements.inclination,
+            right_ascension: elements.right_ascension,
+            argument_of_perigee: elements.argument_of_perigee,
+            mean_anomaly: elements.mean_anomaly,
+            ephemeris_type: elements.ephemeris_type,
+            element_set_number: elements.element_set_number,
+            revolution_number: elements.revolution_number,
+            bstar: elements.drag_term,
+            mean_motion_dot: elements.mean_motion_dot,
+            mean_motion_ddot: elements.mean_motion_ddot,
+        })
+    }
+}
+
+impl From<&ElementSet> for sgp4::Elements {
+    fn from(element_set: &ElementSet) -> Self {
+        Self {
+            object_name: element_set.object_name.clone(),
+            international_designator: element_set.object_id.clone(),
+            norad_id: u64::from(element_set.norad_id),
+            classification: element_set.classification.clone(),
+            datetime: element_set.epoch.naive_utc(),
+            mean_motion_dot: element_set.mean_motion_dot,
+            mean_motion_ddot: element_set.mean_motion_ddot,
+            drag_term: element_set.bstar,
+            element_set_number: element_set.element_set_number,
+            inclination: element_set.inclination,
+            right_ascension: element_set.right_ascension,
+            eccentricity: element_set.eccentricity,
+            argument_of_perigee: element_set.argument_of_perigee,
+            mean_anomaly: element_set.mean_anomaly,
+            mean_motion: element_set.mean_motion,
+            revolution_number: element_set.revolution_number,
+            ephemeris_type: element_set.ephemeris_type,
+        }
+    }
+}
+
+fn decode_alpha5(field: &[u8]) -> Result<u32, ElementSetError> {
+    let invalid = || ElementSetError::TleFormat("invalid Alpha-5 catalog number");
+
+    let leading = ALPHA5_LETTERS
+        .iter()
+        .position(|&letter| letter == field[0].to_ascii_uppercase())
+        .ok_or_else(invalid)?;
+    let rest = std::str::from_utf8(&field[1..])
+        .ok()
+        .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|digits| digits.parse::<u32>().ok())
+        .ok_or_else(invalid)?;
+
+    // the position in the alphabet is at most 23
+    #[allow(clippy::cast_possible_truncation)]
+    let leading = leading as u32 + 10;
+
+    Ok(leading * 10_000 + rest)
+}
+
+fn encode_alpha5(norad_id: u32) -> Result<String, ElementSetError> {
+    match norad_id {
+        0..=99_999 => Ok(format!("{norad_id:05}")),
+        100_000..=MAX_TLE_NORAD_ID => {
+            let letter = ALPHA5_LETTERS[(norad_id / 10_000 - 10) as usize] as char;
+            Ok(format!("{letter}{:04}", norad_id % 10_000))
+        }
+        _ => Err(ElementSetError::NotRepresentable("NORAD_CAT_ID")),
+    }
+}
+
+/// Copy of a TLE line with a zero catalog number and the checksum fixed accordingly.
+fn without_catalog_number(line: &[u8]) -> Result<Vec<u8>, ElementSetError> {
+    if line.len() != TLE_LINE_LENGTH {
+        return Err(ElementSetError::TleFormat(
+            "lines must be 69 characters long",
+        ));
+    }
+    if line[68] != b'0' + checksum(&line[..68]) {
+        return Err(ElementSetError::TleFormat("bad checksum"));
+    }
+
+    let mut line = line.to_vec();
+    line[2..7].copy_from_slice(b"00000");
+    line[68] = b'0' + checksum(&line[..68]);
+
+    Ok(line)
+}
+
+/// Modulo-10 sum of the digits, minus signs counting as one.
+fn checksum(line: &[u8]) -> u8 {
+    let sum: u32 = line
+        .iter()
+        .map(|&c| match c {
+            b'0'..=b'9' => u32::from(c - b'0'),
+            b'-' => 1,
+            _ => 0,
+        })
+        .sum();
+
+    // below 10
+    #[allow(clippy::cast_possible_truncation)]
+    let digit = (sum % 10) as u8;
+    digit
+}
+
+fn with_checksum(line: &str) -> String {
+    let digit = checksum(line.as_bytes());
+    format!("{line}{digit}")
+}
+
+/// `1998-067A` as `98067A`; designators that do not follow the pattern are left out.
+fn tle_designator(object_id: Option<&str>) -> String {
+    object_id
+        .and_then(|id| id.split_once('-'))
+        .filter(|(year, piece)| year.len() == 4 && !piece.is_empty() && piece.len() <= 6)
+        .map(|(year, piece)| format!("{}{piece}", &year[2..]))
+        .unwrap_or_default()
+}
+
+/// Two-digit year and fractional day of the year, e.g. `20194.88612269`.
+fn tle_epoch(epoch: DateTime<Utc>) -> Result<String, ElementSetError> {
+    if !(1957..=2056).contains(&epoch.year()) {
+        return Err(ElementSetError::NotRepresentable("EPOCH"));
+    }
+
+    let midnight = NaiveDate::from_yo_opt(epoch.year(), epoch.ordinal())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .ok_or(ElementSetError::NotRepresentable("EPOCH"))?;
+    let seconds = (epoch.naive_utc() - midnight).as_seconds_f64();
+    let day = f64::from(epoch.ordinal()) + seconds / 86_400.0;
+
+    Ok(format!("{:02}{day:012.8}", epoch.year() % 100))
+}
+
+/// Signed decimal without the leading zero, e.g. `-.00002218`.
+fn tle_mean_motion_dot(value: f64) -> Result<String, ElementSetError> {
+    let formatted = format!("{:.8}", value.abs());
+    let Some(fraction) = formatted.strip_prefix("0.") else {
+        return Err(ElementSetError::NotRepresentable("MEAN_MOTION_DOT"));
+    };
+    let sign = if value < 0.0 && fraction.bytes().any(|b| b != b'0') {
+        '-'
+    } else {
+        ' '
+    };
+
+    Ok(format!("{sign}.{fraction}"))
+}
+
+/// Mantissa with an assumed leading decimal point and a one-digit exponent,
+/// e.g. `-31515-4` for −0.31515 × 10⁻⁴.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn tle_exponential(value: f64, field: &'static str) -> Result<String, ElementSetError> {
+    let sign = if value < 0.0 { '-' } else { ' ' };
+    let magnitude = value.abs();
+
+    if magnitude == 0.0 {
+        return Ok(" 00000-0".to_string());
+    }
+
+    let mut exponent = magnitude.log10().floor() as i32 + 1;
+    let mut mantissa = (magnitude / 10_f64.powi(exponent) * 1e5).round() as u32;
+    if mantissa >= 100_000 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+
+    // too small for the field, which then reads as zero
+    if exponent < -9 {
+        return Ok(" 00000-0".to_string());
+    }
+    if exponent > 9 {
+        return Err(ElementSetError::NotRepresentable(field));
+    }
+
+    let exponent_sign = if exponent < 0 { '-' } else { '+' };
+    Ok(format!(
+        "{sign}{mantissa:05}{exponent_sign}{}",
+        exponent.abs()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS_LINE1: &str = "1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992";
+    const ISS_LINE2: &str = "2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008";
+
+    /// The ISS lines with `catalog_number` in place of 25544 and the checksums fixed.
+    fn with_catalog_number(catalog_number: &str) -> (String, String) {
+        let relabel =
+            |line: &str| with_checksum(&format!("{}{catalog_number}{}", &line[..2], &line[7..68]));
+        (relabel(ISS_LINE1), relabel(ISS_LINE2))
+    }
+
+    #[test]
+    fn tle_round_trip_reproduces_the_lines() {
+        let element_set =
+            ElementSet::from_tle(Some("ISS (ZARYA)".to_string()), ISS_LINE1, ISS_LINE2).unwrap();
+
+        assert_eq!(element_set.norad_id, 25544);
+        assert_eq!(element_set.object_id.as_deref(), Some("1998-067A"));
+
+        let (line1, line2) = element_set.to_tle().unwrap();
+        assert_eq!(line1, ISS_LINE1);
+        assert_eq!(line2, ISS_LINE2);
+    }
+
+    #[test]
+    fn tle_round_trip_keeps_alpha5_catalog_numbers() {
+        for (catalog_number, norad_id) in [("A0001", 100_001), ("Z9999", 339_999)] {
+            let (line1, line2) = with_catalog_number(catalog_number);
+            let element_set = ElementSet::from_tle(None, &line1, &line2).unwrap();
+
+            assert_eq!(element_set.norad_id, norad_id);
+            assert_eq!(element_set.to_tle().unwrap(), (line1, line2));
+        }
+    }
+
+    #[test]
+    fn rejects_a_bad_checksum() {
+        let (mut line1, line2) = with_catalog_number("A0001");
+        let wrong = if line1.ends_with('0') { "1" } else { "0" };
+        line1.replace_range(68.., wrong);
+
+        assert!(matches!(
+            ElementSet::from_tle(None, &line1, &line2),
+            Err(ElementSetError::TleFormat("bad checksum"))
+        ));
+    }
+
+    #[test]
+    fn alpha5_codes_catalog_numbers() {
+        assert_eq!(encode_alpha5(25544).unwrap(), "25544");
+        assert_eq!(encode_alpha5(100_000).unwrap(), "A0000");
+        assert_eq!(encode_alpha5(100_001).unwrap(), "A0001");
+        // I and O are skipped
+        assert_eq!(encode_alpha5(180_000).unwrap(), "J0000");
+        assert_eq!(encode_alpha5(339_999).unwrap(), "Z9999");
+        assert!(encode_alpha5(340_000).is_err());
+
+        assert_eq!(decode_alpha5(b"A0001").unwrap(), 100_001);
+        assert_eq!(decode_alpha5(b"j0000").unwrap(), 180_000);
+        assert_eq!(decode_alpha5(b"Z9999").unwrap(), 339_999);
+        assert!(decode_alpha5(b"I0000").is_err());
+        assert!(decode_alpha5(b"A00X1").is_err());
+    }
+
+    #[test]
+    fn tle_exponential_edge_cases() {
+        assert_eq!(tle_exponential(0.0, "BSTAR").unwrap(), " 00000-0");
+        assert_eq!(tle_exponential(-3.1515e-5, "BSTAR").unwrap(), "-31515-4");
+        assert_eq!(tle_exponential(1.2345e-3, "BSTAR").unwrap(), " 12345-2");
+        assert_eq!(tle_exponential(0.5, "BSTAR").unwrap(), " 50000+0");
+        // the mantissa rounds up to 100000 and carries into the exponent
+        assert_eq!(tle_exponential(9.999_996e-5, "BSTAR").unwrap(), " 10000-3");
+        assert_eq!(tle_exponential(-9.999_996e-5, "BSTAR").unwrap(), "-10000-3");
+        // below the smallest exponent the field reads as zero
+        assert_eq!(tle_exponential(1e-12, "BSTAR").unwrap(), " 00000-0");
+        assert!(matches!(
+            tle_exponential(1e10, "BSTAR"),
+            Err(ElementSetError::NotRepresentable("BSTAR"))
+        ));
+    }
+
+    #[test]
+    fn tle_epoch_counts_days_from_january_first() {
+        let epoch = |text: &str| DateTime::parse_from_rfc3339(text).unwrap().to_utc();
+
+        assert_eq!(
+            tle_epoch(epoch("2020-01-01T00:00:00Z")).unwrap(),
+            "20001.00000000"
+        );
+        assert_eq!(
+            tle_epoch(epoch("2020-12-31T12:00:00Z")).unwrap(),
+            "20366.50000000"
+        );
+        assert_eq!(
+            tle_epoch(epoch("1999-07-01T06:00:00Z")).unwrap(),
+            "99182.25000000"
+        );
+        assert!(tle_epoch(epoch("2057-01-01T00:00:00Z")).is_err());
+    }
+}
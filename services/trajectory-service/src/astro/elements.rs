@@ -0,0 +1,247 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use uom::si::angle::{degree, radian};
+use uom::si::f64::{Angle, Length};
+use uom::si::length::kilometer;
+use uom::si::velocity::kilometer_per_second;
+
+use crate::astro::consts::{A, MU, TWO_PI};
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::models::{
+    DerivedElements, MeanElements, OrbitRegime, OrbitalElements, OsculatingElements,
+};
+use crate::astro::propagator::{Propagator, brouwer_orbit};
+use crate::domain::errors::PropagationError;
+
+/// Below this eccentricity the orbit is treated as circular and the argument of
+/// perigee is undefined (reported as zero, anomalies are then counted from the node).
+const CIRCULAR_ECCENTRICITY: f64 = 1e-10;
+
+/// Within this angle, in radians, of 0° or 180° inclination the orbit is treated as
+/// equatorial and the node is undefined (reported as zero, angles are then counted
+/// from the x axis).
+const EQUATORIAL_INCLINATION: f64 = 1e-10;
+
+impl Propagator {
+    /// Regime of the orbit described by the mean elements, if there are any.
+    pub fn orbit_regime(&self) -> Option<OrbitRegime> {
+        let elements = self.elements.as_ref()?;
+
+        Some(if elements.eccentricity >= 0.25 {
+            OrbitRegime::Heo
+        } else if elements.mean_motion >= 11.25 {
+            OrbitRegime::Leo
+        } else if (0.9..=1.1).contains(&elements.mean_motion) {
+            OrbitRegime::Geo
+        } else {
+            OrbitRegime::Meo
+        })
+    }
+
+    /// Mean elements of the TLE, the orbit size derived from them and the osculating
+    /// elements of the SGP4 state at `datetime`.
+    pub fn orbital_elements_at(
+        &self,
+        datetime: DateTime<Utc>,
+    ) -> Result<OrbitalElements, PropagationError> {
+        let elements = self
+            .elements
+            .as_ref()
+            .ok_or(PropagationError::EphemerisNotSupported)?;
+        let (position, velocity) = self.state_at(datetime)?;
+
+        Ok(OrbitalElements {
+            mean: mean_elements(elements),
+            derived: derived_elements(elements)?,
+            osculating: osculating_elements(datetime, &position, &velocity),
+        })
+    }
+}
+
+fn mean_elements(elements: &sgp4::Elements) -> MeanElements {
+    MeanElements {
+        epoch: elements.datetime.and_utc(),
+        inclination: Angle::new::<degree>(elements.inclination),
+        right_ascension: Angle::new::<degree>(elements.right_ascension),
+        eccentricity: elements.eccentricity,
+        argument_of_perigee: Angle::new::<degree>(elements.argument_of_perigee),
+        mean_anomaly: Angle::new::<degree>(elements.mean_anomaly),
+        mean_motion: elements.mean_motion,
+        bstar: elements.drag_term,
+    }
+}
+
+/// Semi-major axis and period from the Brouwer mean motion SGP4 recovers from the
+/// Kozai one of the TLE; altitudes are above the equatorial radius.
+#[allow(clippy::cast_possible_truncation)]
+fn derived_elements(elements: &sgp4::Elements) -> Result<DerivedElements, PropagationError> {
+    let geopotential = sgp4::WGS84;
+    let orbit = brouwer_orbit(elements)?;
+
+    // a = aₑ (kₑ / n)²ᐟ³, with n in radians per minute
+    let semi_major_axis_km =
+        geopotential.ae * (geopotential.ke / orbit.mean_motion).powf(2.0 / 3.0);
+    let period_minutes = TWO_PI / orbit.mean_motion;
+
+    Ok(DerivedElements {
+        semi_major_axis: Length::new::<kilometer>(semi_major_axis_km),
+        period: TimeDelta::nanoseconds((period_minutes * 60e9).round() as i64),
+        apogee_altitude: Length::new::<kilometer>(
+            semi_major_axis_km * (1.0 + elements.eccentricity) - A,
+        ),
+        perigee_altitude: Length::new::<kilometer>(
+            semi_major_axis_km * (1.0 - elements.eccentricity) - A,
+        ),
+    })
+}
+
+/// Classical elements of the two-body orbit through `position` with `velocity`.
+///
+/// Vallado, *Fundamentals of Astrodynamics and Applications*, Algorithm 9 (RV2COE).
+/// Only elliptic orbits are expected from SGP4, so the mean anomaly uses Kepler's
+/// equation for ellipses.
+pub fn osculating_elements(
+    datetime: DateTime<Utc>,
+    position: &Eci,
+    velocity: &EciVelocity,
+) -> OsculatingElements {
+    let r = [
+        position.x.get::<kilometer>(),
+        position.y.get::<kilometer>(),
+        position.z.get::<kilometer>(),
+    ];
+    let v = [
+        velocity.x.get::<kilometer_per_second>(),
+        velocity.y.get::<kilometer_per_second>(),
+        velocity.z.get::<kilometer_per_second>(),
+    ];
+
+    let r_norm = norm(r);
+    let v_squared = dot(v, v);
+
+    // angular momentum, node vector (k × h) and eccentricity vector
+    let h = cross(r, v);
+    let h_norm = norm(h);
+    let h_unit = h.map(|c| c / h_norm);
+    let node = [-h[1], h[0], 0.0];
+    let radial = v_squared - MU / r_norm;
+    let r_dot_v = dot(r, v);
+    let e_vec: [f64; 3] = std::array::from_fn(|i| (radial * r[i] - r_dot_v * v[i]) / MU);
+    let eccentricity = norm(e_vec);
+
+    let semi_major_axis = 1.0 / (2.0 / r_norm - v_squared / MU);
+    let inclination = (h[2] / h_norm).clamp(-1.0, 1.0).acos();
+
+    // angle from `from` to `to`, counted positively around the angular momentum
+    let angle_between = |from: [f64; 3], to: [f64; 3]| {
+        dot(h_unit, cross(from, to))
+            .atan2(dot(from, to))
+            .rem_euclid(TWO_PI)
+    };
+
+    // prograde or retrograde, the node vector vanishes with sin i
+    let equatorial = norm(node) < h_norm * EQUATORIAL_INCLINATION.sin();
+    let circular = eccentricity < CIRCULAR_ECCENTRICITY;
+
+    // reference direction in the orbit plane for the node-relative angles
+    let line_of_nodes = if equatorial { [1.0, 0.0, 0.0] } else { node };
+
+    let right_ascension = if equatorial {
+        0.0
+    } else {
+        node[1].atan2(node[0]).rem_euclid(TWO_PI)
+    };
+    let (argument_of_perigee, true_anomaly) = if circular {
+        (0.0, angle_between(line_of_nodes, r))
+    } else {
+        (angle_between(line_of_nodes, e_vec), angle_between(e_vec, r))
+    };
+
+    let eccentric_anomaly = ((1.0 - eccentricity * eccentricity).max(0.0).sqrt()
+        * true_anomaly.sin())
+    .atan2(eccentricity + true_anomaly.cos());
+    let mean_anomaly =
+        (eccentric_anomaly - eccentricity * eccentric_anomaly.sin()).rem_euclid(TWO_PI);
+
+    OsculatingElements {
+        datetime,
+        semi_major_axis: Length::new::<kilometer>(semi_major_axis),
+        eccentricity,
+        inclination: Angle::new::<radian>(inclination),
+        right_ascension: Angle::new::<radian>(right_ascension),
+        argument_of_perigee: Angle::new::<radian>(argument_of_perigee),
+        true_anomaly: Angle::new::<radian>(true_anomaly),
+        mean_anomaly: Angle::new::<radian>(mean_anomaly),
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(position: [f64; 3], velocity: [f64; 3]) -> OsculatingElements {
+        let datetime = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        osculating_elements(datetime, &Eci::from(position), &EciVelocity::from(velocity))
+    }
+
+    fn assert_degrees(actual: Angle, expected: f64, tolerance: f64, name: &str) {
+        let actual = actual.get::<degree>();
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "{name} is {actual}°, expected {expected}°"
+        );
+    }
+
+    #[test]
+    fn matches_vallado_example_2_5() {
+        let elements = elements(
+            [6524.834, 6862.875, 6448.296],
+            [4.901_327, 5.533_756, -1.976_341],
+        );
+
+        assert!((elements.semi_major_axis.get::<kilometer>() - 36_127.343).abs() < 0.5);
+        assert!((elements.eccentricity - 0.832_853).abs() < 1e-6);
+        assert_degrees(elements.inclination, 87.870, 1e-3, "inclination");
+        assert_degrees(elements.right_ascension, 227.898, 1e-3, "RAAN");
+        assert_degrees(
+            elements.argument_of_perigee,
+            53.38,
+            1e-2,
+            "argument of perigee",
+        );
+        assert_degrees(elements.true_anomaly, 92.335, 1e-3, "true anomaly");
+    }
+
+    #[test]
+    fn counts_retrograde_equatorial_angles_from_the_x_axis() {
+        // perigee on +y, moving towards −x: angles grow clockwise seen from +z
+        let elements = elements([0.0, 7000.0, 0.0], [8.0, 0.0, 0.0]);
+
+        assert_degrees(elements.inclination, 180.0, 1e-9, "inclination");
+        assert_degrees(elements.right_ascension, 0.0, 1e-9, "RAAN");
+        assert_degrees(
+            elements.argument_of_perigee,
+            270.0,
+            1e-9,
+            "argument of perigee",
+        );
+        assert_degrees(elements.true_anomaly, 0.0, 1e-9, "true anomaly");
+    }
+}
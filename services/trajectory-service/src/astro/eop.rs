@@ -0,0 +1,194 @@
+//! Earth orientation parameters (EOP) published by the IERS.
+//!
+//! UT1 − UTC and the polar motion coordinates are read from the daily rows of the
+//! `finals2000A.*` / `finals.*` files of the IERS Rapid Service (Bulletin A values,
+//! including predictions) and interpolated linearly between days.
+//!
+//! See: [IERS data products](https://www.iers.org/IERS/EN/DataProducts/EarthOrientationData/eop.html)
+
+use chrono::{DateTime, Utc};
+use uom::si::angle::radian;
+use uom::si::f64::Angle;
+
+use crate::astro::consts::ARCSEC_TO_RAD;
+use crate::astro::coords::ecef::Ecef;
+use crate::astro::coords::eci::Eci;
+use crate::astro::coords::frames::{EarthFixedFrame, PolarMotion};
+use crate::astro::rotation::Matrix3;
+use crate::astro::time::{JulianDate, TimeScale, gmst};
+
+/// Earth orientation at a given instant.
+///
+/// The default (UT1 = UTC, no polar motion) is what the conversions fall back to
+/// when no parameters are available.
+#[derive(Clone, Copy, Default)]
+pub struct EarthOrientation {
+    /// UT1 − UTC, in seconds.
+    pub ut1_minus_utc: f64,
+    pub polar_motion: PolarMotion,
+}
+
+impl EarthOrientation {
+    /// Greenwich mean sidereal time at the UTC instant `datetime`, evaluated on UT1.
+    pub fn gst(&self, datetime: DateTime<Utc>) -> Angle {
+        gmst(JulianDate::in_scale(
+            datetime,
+            TimeScale::Ut1,
+            self.ut1_minus_utc,
+        ))
+    }
+
+    /// Rotation from PEF into the ITRF.
+    pub fn polar_motion_matrix(&self) -> Matrix3 {
+        EarthFixedFrame::Itrf.rotation_from_pef(self.polar_motion)
+    }
+
+    /// Rotate a TEME position into the ITRF.
+    pub fn teme_to_itrf(&self, eci: &Eci, datetime: DateTime<Utc>) -> Ecef {
+        eci.to_ecef(self.gst(datetime))
+            .rotated(&self.polar_motion_matrix())
+    }
+}
+
+/// One daily row of the IERS file.
+struct EopRecord {
+    mjd: f64,
+    ut1_minus_utc: f64,
+    /// Polar motion, in arcseconds.
+    xp: f64,
+    yp: f64,
+}
+
+/// Daily Earth orientation parameters, sorted by date.
+pub struct EopTable {
+    records: Vec<EopRecord>,
+}
+
+impl EopTable {
+    /// Table without any data: every instant falls back to the default orientation.
+    pub const fn empty() -> Self {
+        Self {
+            records: Vec::new(),
+        }
+    }
+
+    /// Parse the fixed-width `finals` format.
+    ///
+    /// Rows without UT1 − UTC or polar motion (the far end of the prediction span)
+    /// are skipped, as are rows that are not in date order.
+    pub fn parse_finals(text: &str) -> Self {
+        let field = |line: &str, from: usize, to: usize| {
+            line.get(from..to)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+        };
+
+        let mut records: Vec<EopRecord> = Vec::new();
+        for line in text.lines() {
+            let (Some(mjd), Some(xp), Some(yp), Some(ut1_minus_utc)) = (
+                field(line, 7, 15),
+                field(line, 18, 27),
+                field(line, 37, 46),
+                field(line, 58, 68),
+            ) else {
+                continue;
+            };
+
+            if records.last().is_none_or(|last| last.mjd < mjd) {
+                records.push(EopRecord {
+                    mjd,
+                    ut1_minus_utc,
+                    xp,
+                    yp,
+                });
+            }
+        }
+
+        Self { records }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether `datetime` lies within the span of the table.
+    pub fn covers(&self, datetime: DateTime<Utc>) -> bool {
+        self.bracket(modified_julian_date(datetime)).is_some()
+    }
+
+    /// Earth orientation at `datetime`, or the default one outside the table.
+    ///
+    /// UT1 − UTC jumps by one second on the day after a leap second; the jump is
+    /// removed before interpolating so the day before keeps a continuous value.
+    pub fn orientation_at(&self, datetime: DateTime<Utc>) -> EarthOrientation {
+        let mjd = modified_julian_date(datetime);
+        let Some((before, after)) = self.bracket(mjd) else {
+            return EarthOrientation::default();
+        };
+
+        let fraction = if after.mjd > before.mjd {
+            (mjd - before.mjd) / (after.mjd - before.mjd)
+        } else {
+            0.0
+        };
+        let interpolate = |a: f64, b: f64| fraction.mul_add(b - a, a);
+
+        let leap = (after.ut1_minus_utc - before.ut1_minus_utc).round();
+
+        EarthOrientation {
+            ut1_minus_utc: interpolate(before.ut1_minus_utc, after.ut1_minus_utc - leap),
+            polar_motion: PolarMotion {
+                xp: Angle::new::<radian>(interpolate(before.xp, after.xp) * ARCSEC_TO_RAD),
+                yp: Angle::new::<radian>(interpolate(before.yp, after.yp) * ARCSEC_TO_RAD),
+            },
+        }
+    }
+
+    /// Records on either side of `mjd`, `None` outside the table.
+    fn bracket(&self, mjd: f64) -> Option<(&EopRecord, &EopRecord)> {
+        let (first, last) = (self.records.first()?, self.records.last()?);
+        if mjd < first.mjd || mjd > last.mjd {
+            return None;
+        }
+
+        // first record strictly after `mjd`, none when exactly on the last one
+        let after = self.records.partition_point(|record| record.mjd <= mjd);
+        let before = self.records.get(after.checked_sub(1)?)?;
+        Some((before, self.records.get(after).unwrap_or(before)))
+    }
+}
+
+fn modified_julian_date(datetime: DateTime<Utc>) -> f64 {
+    JulianDate::from_datetime(&datetime).modified()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two days of `finals2000A.data`, including the LOD and nutation columns.
+    const FINALS: &str = "\
+20 1 1 58849.00 I  0.076577 0.000045  0.282329 0.000044  I-0.1771657 0.0000115  0.3125 0.0072  I     0.171    0.196     0.096    0.026
+20 1 2 58850.00 I  0.075179 0.000045  0.283157 0.000044  I-0.1775398 0.0000091  0.4259 0.0066  I     0.165    0.196     0.098    0.026
+";
+
+    #[test]
+    fn parses_finals2000a_columns() {
+        let table = EopTable::parse_finals(FINALS);
+
+        assert_eq!(table.len(), 2);
+        let first = &table.records[0];
+        assert!((first.mjd - 58_849.0).abs() < f64::EPSILON);
+        assert!((first.xp - 0.076_577).abs() < f64::EPSILON);
+        assert!((first.yp - 0.282_329).abs() < f64::EPSILON);
+        assert!((first.ut1_minus_utc + 0.177_165_7).abs() < f64::EPSILON);
+        assert!((table.records[1].ut1_minus_utc + 0.177_539_8).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn finds_nothing_in_other_formats() {
+        // IERS EOP 14 C04, whitespace-separated
+        let c04 = "2020   1   1  58849   0.076577   0.282329  -0.1771657   0.0003125";
+
+        assert_eq!(EopTable::parse_finals(c04).len(), 0);
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use uom::si::length::kilometer;
+
+use crate::astro::consts::{A, SUN_RADIUS};
+use crate::astro::coords::eci::Eci;
+use crate::astro::propagator::Propagator;
+use crate::astro::sun::sun_eci;
+use crate::domain::errors::PropagationError;
+
+/// Illumination state of a satellite with respect to Earth's shadow.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Illumination {
+    Sunlit,
+    Penumbra,
+    Umbra,
+}
+
+/// Apparent geometry of the Earth and Sun disks as seen from the satellite.
+///
+/// Conical shadow model with a spherical Earth: the satellite is in umbra when the
+/// Sun disk is completely hidden behind the Earth disk, and in penumbra while the
+/// two disks overlap. All angles are in radians.
+pub struct Shadow {
+    /// Angle between the directions to the Earth centre and to the Sun centre.
+    pub separation: f64,
+    /// Apparent angular radius of the Earth.
+    pub earth_radius: f64,
+    /// Apparent angular radius of the Sun.
+    pub sun_radius: f64,
+}
+
+impl Shadow {
+    pub fn between(satellite: &Eci, sun: &Eci) -> Self {
+        let r = [
+            satellite.x.get::<kilometer>(),
+            satellite.y.get::<kilometer>(),
+            satellite.z.get::<kilometer>(),
+        ];
+        // satellite to sun
+        let s = [
+            sun.x.get::<kilometer>() - r[0],
+            sun.y.get::<kilometer>() - r[1],
+            sun.z.get::<kilometer>() - r[2],
+        ];
+
+        let r_norm = r.iter().map(|c| c * c).sum::<f64>().sqrt();
+        let s_norm = s.iter().map(|c| c * c).sum::<f64>().sqrt();
+
+        // -r points from the satellite to the Earth centre
+        let cos_separation = -(r[0] * s[0] + r[1] * s[1] + r[2] * s[2]) / (r_norm * s_norm);
+
+        Self {
+            separation: cos_separation.clamp(-1.0, 1.0).acos(),
+            earth_radius: (A / r_norm).min(1.0).asin(),
+            sun_radius: (SUN_RADIUS / s_norm).asin(),
+        }
+    }
+
+    /// Negative while any part of the Sun disk is hidden.
+    pub fn penumbra_margin(&self) -> f64 {
+        self.separation - (self.earth_radius + self.sun_radius)
+    }
+
+    /// Negative while the whole Sun disk is hidden.
+    pub fn umbra_margin(&self) -> f64 {
+        self.separation - (self.earth_radius - self.sun_radius)
+    }
+
+    pub fn illumination(&self) -> Illumination {
+        if self.umbra_margin() < 0.0 {
+            Illumination::Umbra
+        } else if self.penumbra_margin() < 0.0 {
+            Illumination::Penumbra
+        } else {
+            Illumination::Sunlit
+        }
+    }
+}
+
+impl Propagator {
+    pub fn shadow_at(&self, datetime: DateTime<Utc>) -> Result<Shadow, PropagationError> {
+        let eci = self.eci_at(datetime)?;
+        Ok(Shadow::between(&eci, &sun_eci(datetime)))
+    }
+}
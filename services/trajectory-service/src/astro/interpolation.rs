@@ -0,0 +1,479 @@
+//! Ephemerides supplied as tables of state vectors, such as operator-provided CCSDS
+//! OEMs, propagated by polynomial interpolation between the tabulated states.
+//!
+//! States are converted to TEME when the ephemeris is built, so the interpolated
+//! states can be used wherever SGP4 output is.
+
+use chrono::{DateTime, Utc};
+use std::fmt::{self, Formatter};
+
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::coords::frames::InertialFrame;
+use crate::astro::models::{PropagationMethod, PropagationModel};
+use crate::astro::propagator::OrbitPropagator;
+use crate::astro::rotation::{apply, transpose};
+use crate::astro::time::seconds_between;
+use crate::domain::errors::{EphemerisError, PropagationError};
+
+/// Highest interpolation degree accepted; higher ones oscillate between the states.
+pub const MAX_INTERPOLATION_DEGREE: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    /// Positions and velocities interpolated separately through `degree + 1` states.
+    Lagrange,
+    /// Positions interpolated with matching velocities through `degree / 2 + 1`
+    /// states (at least two), velocities being the derivative of the position
+    /// polynomial.
+    Hermite,
+}
+
+impl InterpolationMethod {
+    /// Degree used when the ephemeris does not give one.
+    pub const fn default_degree(self) -> usize {
+        match self {
+            Self::Lagrange => 7,
+            Self::Hermite => 5,
+        }
+    }
+
+    /// Number of states an interpolation of `degree` uses.
+    const fn points(self, degree: usize) -> usize {
+        match self {
+            Self::Lagrange => degree + 1,
+            Self::Hermite => {
+                if degree < 2 {
+                    2
+                } else {
+                    degree / 2 + 1
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for InterpolationMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lagrange => write!(f, "LAGRANGE"),
+            Self::Hermite => write!(f, "HERMITE"),
+        }
+    }
+}
+
+/// Position (km) and velocity (km/s) at an instant.
+#[derive(Debug, Clone, Copy)]
+pub struct EphemerisState {
+    pub epoch: DateTime<Utc>,
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+pub struct Ephemeris {
+    pub object_name: Option<String>,
+    pub norad_id: Option<u32>,
+    method: InterpolationMethod,
+    /// States used by every interpolation.
+    points: usize,
+    /// TEME states in strictly increasing epoch order.
+    states: Vec<EphemerisState>,
+    start: DateTime<Utc>,
+    stop: DateTime<Utc>,
+}
+
+impl Ephemeris {
+    /// Check the states and convert them from `frame` to TEME.
+    ///
+    /// The coverage spans the first to the last state.
+    pub fn new(
+        object_name: Option<String>,
+        norad_id: Option<u32>,
+        frame: InertialFrame,
+        method: InterpolationMethod,
+        degree: usize,
+        mut states: Vec<EphemerisState>,
+    ) -> Result<Self, EphemerisError> {
+        if degree == 0 || degree > MAX_INTERPOLATION_DEGREE {
+            return Err(EphemerisError::InvalidField(
+                "interpolation degree",
+                degree.to_string(),
+            ));
+        }
+
+        let points = method.points(degree);
+        if states.len() < points {
+            return Err(EphemerisError::TooFewStates {
+                method,
+                degree,
+                required: points,
+                found: states.len(),
+            });
+        }
+
+        for pair in states.windows(2) {
+            if pair[1].epoch <= pair[0].epoch {
+                return Err(EphemerisError::UnorderedStates(pair[1].epoch));
+            }
+        }
+
+        for state in &mut states {
+            if !state
+                .position
+                .iter()
+                .chain(&state.velocity)
+                .all(|c| c.is_finite())
+            {
+                return Err(EphemerisError::InvalidField(
+                    "state vector",
+                    state.epoch.to_string(),
+                ));
+            }
+
+            if frame != InertialFrame::Teme {
+                let to_teme = transpose(&frame.rotation_from_teme(state.epoch));
+                state.position = apply(&to_teme, state.position);
+                state.velocity = apply(&to_teme, state.velocity);
+            }
+        }
+
+        let (start, stop) = match (states.first(), states.last()) {
+            (Some(first), Some(last)) => (first.epoch, last.epoch),
+            _ => return Err(EphemerisError::Malformed("no states")),
+        };
+
+        Ok(Self {
+            object_name,
+            norad_id,
+            method,
+            points,
+            states,
+            start,
+            stop,
+        })
+    }
+
+    /// Narrow the coverage to a usable span within the states, e.g. one that leaves
+    /// out the edges where the interpolation cannot be centred.
+    pub fn with_usable_span(
+        mut self,
+        start: Option<DateTime<Utc>>,
+        stop: Option<DateTime<Utc>>,
+    ) -> Result<Self, EphemerisError> {
+        self.start = start.map_or(self.start, |start| start.max(self.start));
+        self.stop = stop.map_or(self.stop, |stop| stop.min(self.stop));
+
+        if self.start > self.stop {
+            return Err(EphemerisError::Malformed(
+                "usable span outside of the states",
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// First and last instants that can be interpolated.
+    pub const fn coverage(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.start, self.stop)
+    }
+
+    pub const fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// States around `datetime`, as many on each side as the table edges allow.
+    fn window(&self, datetime: DateTime<Utc>) -> &[EphemerisState] {
+        let after = self.states.partition_point(|state| state.epoch <= datetime);
+        let first = after
+            .saturating_sub(self.points / 2)
+            .min(self.states.len() - self.points);
+
+        &self.states[first..first + self.points]
+    }
+}
+
+impl OrbitPropagator for Ephemeris {
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError> {
+        if datetime < self.start || datetime > self.stop {
+            return Err(PropagationError::OutsideEphemerisCoverage {
+                datetime,
+                start: self.start,
+                stop: self.stop,
+            });
+        }
+
+        let window = self.window(datetime);
+        // abscissae relative to the requested instant, which is then at zero
+        let times: Vec<f64> = window
+            .iter()
+            .map(|state| seconds_between(datetime, state.epoch))
+            .collect();
+
+        let (position, velocity) = match self.method {
+            InterpolationMethod::Lagrange => {
+                let weights = lagrange_weights(&times);
+                let combine = |component: fn(&EphemerisState) -> f64| {
+                    window
+                        .iter()
+                        .zip(&weights)
+                        .map(|(state, weight)| weight * component(state))
+                        .sum::<f64>()
+                };
+
+                (
+                    [
+                        combine(|s| s.position[0]),
+                        combine(|s| s.position[1]),
+                        combine(|s| s.position[2]),
+                    ],
+                    [
+                        combine(|s| s.velocity[0]),
+                        combine(|s| s.velocity[1]),
+                        combine(|s| s.velocity[2]),
+                    ],
+                )
+            }
+            InterpolationMethod::Hermite => {
+                let mut position = [0.0; 3];
+                let mut velocity = [0.0; 3];
+                for axis in 0..3 {
+                    let values: Vec<(f64, f64)> = window
+                        .iter()
+                        .map(|state| (state.position[axis], state.velocity[axis]))
+                        .collect();
+                    (position[axis], velocity[axis]) = hermite_at_zero(&times, &values);
+                }
+                (position, velocity)
+            }
+        };
+
+        Ok((Eci::from(position), EciVelocity::from(velocity)))
+    }
+
+    fn method(&self) -> PropagationMethod {
+        PropagationMethod {
+            model: match self.method {
+                InterpolationMethod::Lagrange => PropagationModel::Lagrange,
+                InterpolationMethod::Hermite => PropagationModel::Hermite,
+            },
+            resonance: None,
+            gravity_model: None,
+            afspc_compatibility_mode: false,
+        }
+    }
+}
+
+/// Lagrange basis polynomials through `times` evaluated at zero.
+fn lagrange_weights(times: &[f64]) -> Vec<f64> {
+    times
+        .iter()
+        .enumerate()
+        .map(|(k, &tk)| {
+            times
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != k)
+                .map(|(_, &tj)| -tj / (tk - tj))
+                .product()
+        })
+        .collect()
+}
+
+/// Value and derivative at zero of the Hermite polynomial matching the (value,
+/// derivative) pairs at `times`, from its Newton form over the doubled nodes.
+fn hermite_at_zero(times: &[f64], values: &[(f64, f64)]) -> (f64, f64) {
+    let n = 2 * times.len();
+    let nodes: Vec<f64> = times.iter().flat_map(|&t| [t, t]).collect();
+
+    // divided differences, a repeated node taking the derivative
+    let mut table: Vec<f64> = values
+        .iter()
+        .flat_map(|&(value, _)| [value, value])
+        .collect();
+    let mut coefficients = vec![table[0]];
+    for order in 1..n {
+        for i in (order..n).rev() {
+            table[i] = if order == 1 && i % 2 == 1 {
+                values[i / 2].1
+            } else {
+                (table[i] - table[i - 1]) / (nodes[i] - nodes[i - order])
+            };
+        }
+        coefficients.push(table[order]);
+    }
+
+    // Horner's scheme for the value and its derivative
+    let mut value = coefficients[n - 1];
+    let mut derivative = 0.0;
+    for k in (0..n - 1).rev() {
+        derivative = derivative * -nodes[k] + value;
+        value = value * -nodes[k] + coefficients[k];
+    }
+
+    (value, derivative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use uom::si::length::kilometer;
+    use uom::si::velocity::kilometer_per_second;
+
+    use crate::astro::consts::MU;
+
+    const STEP_SECONDS: i64 = 60;
+    const STATES: i64 = 40;
+
+    /// Two-body state `seconds` after perigee on an inclined orbit with a = 7500 km and
+    /// e = 0.1, solving Kepler's equation by Newton's method.
+    fn keplerian_state(seconds: f64) -> ([f64; 3], [f64; 3]) {
+        let (semi_major_axis, eccentricity) = (7500.0_f64, 0.1_f64);
+        let (sin_i, cos_i) = 51.6_f64.to_radians().sin_cos();
+        let mean_motion = (MU / semi_major_axis.powi(3)).sqrt();
+        let mean_anomaly = mean_motion * seconds;
+
+        let mut anomaly = mean_anomaly;
+        for _ in 0..20 {
+            anomaly -= (anomaly - eccentricity * anomaly.sin() - mean_anomaly)
+                / (1.0 - eccentricity * anomaly.cos());
+        }
+        let (sin_e, cos_e) = anomaly.sin_cos();
+        let semi_minor_axis = semi_major_axis * (1.0 - eccentricity.powi(2)).sqrt();
+        let anomaly_rate = mean_motion / (1.0 - eccentricity * cos_e);
+
+        // in the orbital plane, perigee along x, then tilted about the x axis
+        let in_plane = [
+            semi_major_axis * (cos_e - eccentricity),
+            semi_minor_axis * sin_e,
+        ];
+        let in_plane_velocity = [
+            -semi_major_axis * sin_e * anomaly_rate,
+            semi_minor_axis * cos_e * anomaly_rate,
+        ];
+        let tilt = |[along, across]: [f64; 2]| [along, across * cos_i, across * sin_i];
+
+        (tilt(in_plane), tilt(in_plane_velocity))
+    }
+
+    fn start() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+            .unwrap()
+            .to_utc()
+    }
+
+    fn at(seconds: f64) -> DateTime<Utc> {
+        #[allow(clippy::cast_possible_truncation)]
+        let nanoseconds = (seconds * 1e9).round() as i64;
+        start() + TimeDelta::nanoseconds(nanoseconds)
+    }
+
+    fn ephemeris(method: InterpolationMethod) -> Ephemeris {
+        let states = (0..STATES)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let seconds = (i * STEP_SECONDS) as f64;
+                let (position, velocity) = keplerian_state(seconds);
+                EphemerisState {
+                    epoch: at(seconds),
+                    position,
+                    velocity,
+                }
+            })
+            .collect();
+
+        Ephemeris::new(
+            None,
+            None,
+            InertialFrame::Teme,
+            method,
+            method.default_degree(),
+            states,
+        )
+        .unwrap()
+    }
+
+    /// Position (km) and velocity (km/s) errors of the interpolation at `seconds`.
+    fn errors(ephemeris: &Ephemeris, seconds: f64) -> (f64, f64) {
+        let (position, velocity) = ephemeris.state_at(at(seconds)).unwrap();
+        let (expected_position, expected_velocity) = keplerian_state(seconds);
+
+        let position = [position.x, position.y, position.z].map(|c| c.get::<kilometer>());
+        let velocity =
+            [velocity.x, velocity.y, velocity.z].map(|c| c.get::<kilometer_per_second>());
+        let distance = |a: [f64; 3], b: [f64; 3]| {
+            a.iter()
+                .zip(&b)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        (
+            distance(position, expected_position),
+            distance(velocity, expected_velocity),
+        )
+    }
+
+    #[test]
+    fn interpolates_a_keplerian_arc() {
+        #[allow(clippy::cast_precision_loss)]
+        let (interval, last) = (STEP_SECONDS as f64, ((STATES - 1) * STEP_SECONDS) as f64);
+
+        for method in [InterpolationMethod::Lagrange, InterpolationMethod::Hermite] {
+            let ephemeris = ephemeris(method);
+
+            // the edges are on the states, the intervals next to them are interpolated
+            // from off-centre windows, and the middle of the arc from a centred one
+            for seconds in [0.0, interval / 2.0, last / 2.0, last - interval / 2.0, last] {
+                let (position_error, velocity_error) = errors(&ephemeris, seconds);
+
+                assert!(
+                    position_error < 1e-5,
+                    "{method} position off by {position_error} km at {seconds} s"
+                );
+                assert!(
+                    velocity_error < 1e-7,
+                    "{method} velocity off by {velocity_error} km/s at {seconds} s"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reproduces_polynomials_of_the_interpolation_degree() {
+        let cubic = |t: f64| 2.0 * t.powi(3) - t * t + 3.0 * t - 5.0;
+        let cubic_rate = |t: f64| 6.0 * t * t - 2.0 * t + 3.0;
+        let times = [-1.5, -0.5, 0.5, 1.5];
+
+        let weights = lagrange_weights(&times);
+        let value: f64 = times.iter().zip(&weights).map(|(&t, w)| w * cubic(t)).sum();
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((value - cubic(0.0)).abs() < 1e-12);
+
+        let values: Vec<(f64, f64)> = times[1..3]
+            .iter()
+            .map(|&t| (cubic(t), cubic_rate(t)))
+            .collect();
+        let (value, derivative) = hermite_at_zero(&times[1..3], &values);
+        assert!((value - cubic(0.0)).abs() < 1e-12);
+        assert!((derivative - cubic_rate(0.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_times_outside_the_coverage() {
+        let ephemeris = ephemeris(InterpolationMethod::Lagrange);
+        let (start, stop) = ephemeris.coverage();
+
+        for datetime in [
+            start - TimeDelta::milliseconds(1),
+            stop + TimeDelta::milliseconds(1),
+        ] {
+            assert!(matches!(
+                ephemeris.state_at(datetime),
+                Err(PropagationError::OutsideEphemerisCoverage { .. })
+            ));
+        }
+        assert!(ephemeris.state_at(start).is_ok());
+        assert!(ephemeris.state_at(stop).is_ok());
+    }
+}
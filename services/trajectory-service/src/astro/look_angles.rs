@@ -1,45 +1,114 @@
 use chrono::{DateTime, Utc};
+use std::f64::consts::PI;
 use uom::si::angle::radian;
-use uom::si::f64::{Angle, Length};
+use uom::si::f64::{Angle, Frequency, Length, Velocity};
 use uom::si::length::kilometer;
+use uom::si::velocity::kilometer_per_second;
 
-use crate::astro;
-use crate::astro::consts::TWO_PI;
-use crate::astro::coords::ecef::Ecef;
+use crate::astro::consts::{SPEED_OF_LIGHT, TWO_PI};
+use crate::astro::coords::ecef::{Ecef, EcefVelocity};
+use crate::astro::coords::frames::InertialFrame;
 use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::{EarthOrientation, EopTable};
 use crate::astro::models::LookAngles;
 use crate::astro::propagator::Propagator;
+use crate::astro::refraction::Atmosphere;
+use crate::astro::rotation;
 use crate::domain::errors::PropagationError;
 
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct LookAnglesComputation {
     pub azimuth: bool,
     pub elevation: bool,
+    pub apparent_elevation: bool,
     pub range: bool,
+    pub range_rate: bool,
+    pub downlink_frequency: bool,
+    pub uplink_frequency: bool,
+    /// Carrier frequency the Doppler-shifted frequencies are derived from.
+    pub nominal_frequency: Option<Frequency>,
+    /// Conditions used for the apparent elevation; standard ones when not given.
+    pub atmosphere: Option<Atmosphere>,
+    pub right_ascension: bool,
+    pub declination: bool,
+    pub hour_angle: bool,
+    pub equatorial_frame: InertialFrame,
 }
 
-impl Propagator {
-    pub fn look_angles_at(
-        &self,
-        datetime: DateTime<Utc>,
+impl LookAnglesComputation {
+    const fn needs_range_rate(&self) -> bool {
+        self.range_rate || self.downlink_frequency || self.uplink_frequency
+    }
+
+    const fn needs_equatorial(&self) -> bool {
+        self.right_ascension || self.declination || self.hour_angle
+    }
+}
+
+/// Topocentric equatorial coordinates of a target as seen by an observer.
+pub struct Equatorial {
+    pub right_ascension: Angle,
+    pub declination: Angle,
+    /// Local hour angle, west of the meridian, within (-180°, 180°].
+    pub hour_angle: Angle,
+}
+
+impl Equatorial {
+    /// Express the observer-to-target vector in an inertial frame.
+    ///
+    /// The hour angle is always taken from the right ascension of date, as the local
+    /// sidereal time it is measured against refers to the equinox of date.
+    pub fn observe(
         observer: &Geodetic,
-        compute: &LookAnglesComputation,
-    ) -> Result<LookAngles, PropagationError> {
-        if !compute.azimuth && !compute.elevation && !compute.range {
-            return Ok(LookAngles {
-                azimuth: None,
-                elevation: None,
-                range: None,
-            });
+        target: &Ecef,
+        orientation: &EarthOrientation,
+        frame: InertialFrame,
+        datetime: DateTime<Utc>,
+    ) -> Self {
+        let gst = orientation.gst(datetime);
+        let rho = (target.clone() - Ecef::from(observer))
+            .rotated(&rotation::transpose(&orientation.polar_motion_matrix()))
+            .to_eci(gst);
+        let rho_km = [
+            rho.x.get::<kilometer>(),
+            rho.y.get::<kilometer>(),
+            rho.z.get::<kilometer>(),
+        ];
+
+        let local_sidereal_time = gst.get::<radian>() + observer.lon.get::<radian>();
+        let hour_angle = local_sidereal_time - rho_km[1].atan2(rho_km[0]);
+
+        let rho_km = rotation::apply(&frame.rotation_from_teme(datetime), rho_km);
+        let range_km = rho_km
+            .iter()
+            .map(|c| c * c)
+            .sum::<f64>()
+            .sqrt()
+            .max(f64::EPSILON);
+
+        Self {
+            right_ascension: Angle::new::<radian>(rho_km[1].atan2(rho_km[0]).rem_euclid(TWO_PI)),
+            declination: Angle::new::<radian>((rho_km[2] / range_km).asin()),
+            hour_angle: Angle::new::<radian>(PI - (PI - hour_angle).rem_euclid(TWO_PI)),
         }
+    }
+}
 
-        let eci = self.eci_at(datetime)?;
-        let gst = astro::time::utc_to_gst(datetime);
+/// Topocentric horizon coordinates of a target as seen by an observer.
+pub struct Horizon {
+    pub azimuth: Angle,
+    pub elevation: Angle,
+    pub range: Length,
+}
 
-        let sat_ecef = eci.to_ecef(gst);
+impl Horizon {
+    /// Project the observer-to-target vector onto the observer's local
+    /// South-East-Zenith (SEZ) frame.
+    pub fn observe(observer: &Geodetic, target: Ecef) -> Self {
         let obs_ecef = Ecef::from(observer);
 
-        let rho = sat_ecef - obs_ecef;
+        let rho = target - obs_ecef;
 
         let sin_lat = observer.lat.sin();
         let cos_lat = observer.lat.cos();
@@ -54,38 +123,174 @@ impl Propagator {
         let e_km = e.get::<kilometer>();
         let z_km = z.get::<kilometer>();
 
-        let range_km = if compute.range || compute.elevation {
+        let range_km = {
             let r = (s_km * s_km + e_km * e_km + z_km * z_km).sqrt();
             // prevent division by zero (practically impossible)
             if r == 0.0 { f64::EPSILON } else { r }
-        } else {
-            0.0
         };
 
-        let azimuth = if compute.azimuth {
-            let az_rad = e_km.atan2(s_km).rem_euclid(TWO_PI);
-            Some(Angle::new::<radian>(az_rad))
-        } else {
-            None
-        };
+        let az_rad = e_km.atan2(s_km).rem_euclid(TWO_PI);
+        let el_rad = (z_km / range_km).asin();
 
-        let elevation = if compute.elevation {
-            let el_rad = (z_km / range_km).asin();
-            Some(Angle::new::<radian>(el_rad))
-        } else {
-            None
-        };
+        Self {
+            azimuth: Angle::new::<radian>(az_rad),
+            elevation: Angle::new::<radian>(el_rad),
+            range: Length::new::<kilometer>(range_km),
+        }
+    }
+}
+
+/// Rate of change of the observer-to-target distance, positive while receding.
+///
+/// Both vectors are Earth-fixed: the observer is at rest in that frame, so its velocity
+/// due to Earth rotation is already accounted for by the `ω × r` term of the target's
+/// Earth-fixed velocity.
+pub fn range_rate(observer: &Geodetic, target: &Ecef, velocity: &EcefVelocity) -> Velocity {
+    let rho = target.clone() - Ecef::from(observer);
+
+    let rho_km = [
+        rho.x.get::<kilometer>(),
+        rho.y.get::<kilometer>(),
+        rho.z.get::<kilometer>(),
+    ];
+    let rate_km_s = [
+        velocity.x.get::<kilometer_per_second>(),
+        velocity.y.get::<kilometer_per_second>(),
+        velocity.z.get::<kilometer_per_second>(),
+    ];
+
+    let range_km = rho_km
+        .iter()
+        .map(|c| c * c)
+        .sum::<f64>()
+        .sqrt()
+        .max(f64::EPSILON);
+    let projected: f64 = rho_km.iter().zip(rate_km_s).map(|(r, v)| r * v).sum();
+
+    Velocity::new::<kilometer_per_second>(projected / range_km)
+}
+
+/// Frequency received on the ground for a satellite transmitting at `nominal`.
+///
+/// First-order (non-relativistic) Doppler shift, `f = f₀ · (1 − ṙ / c)`.
+pub fn downlink_frequency(nominal: Frequency, range_rate: Velocity) -> Frequency {
+    nominal * (1.0 - range_rate.get::<kilometer_per_second>() / SPEED_OF_LIGHT)
+}
+
+/// Frequency to transmit from the ground so that the satellite receives `nominal`.
+pub fn uplink_frequency(nominal: Frequency, range_rate: Velocity) -> Frequency {
+    nominal / (1.0 - range_rate.get::<kilometer_per_second>() / SPEED_OF_LIGHT)
+}
+
+impl Propagator {
+    pub fn horizon_at(
+        &self,
+        datetime: DateTime<Utc>,
+        observer: &Geodetic,
+        eop: &EopTable,
+    ) -> Result<Horizon, PropagationError> {
+        let eci = self.eci_at(datetime)?;
+        let ecef = eop.orientation_at(datetime).teme_to_itrf(&eci, datetime);
+
+        Ok(Horizon::observe(observer, ecef))
+    }
+
+    /// Horizon coordinates with the elevation raised by refraction when an atmosphere is given.
+    pub fn observed_horizon_at(
+        &self,
+        datetime: DateTime<Utc>,
+        observer: &Geodetic,
+        atmosphere: Option<&Atmosphere>,
+        eop: &EopTable,
+    ) -> Result<Horizon, PropagationError> {
+        let mut horizon = self.horizon_at(datetime, observer, eop)?;
+        if let Some(atmosphere) = atmosphere {
+            horizon.elevation = atmosphere.apparent_elevation(horizon.elevation);
+        }
+        Ok(horizon)
+    }
+
+    pub fn look_angles_at(
+        &self,
+        datetime: DateTime<Utc>,
+        observer: &Geodetic,
+        compute: &LookAnglesComputation,
+        eop: &EopTable,
+    ) -> Result<LookAngles, PropagationError> {
+        if !compute.azimuth
+            && !compute.elevation
+            && !compute.apparent_elevation
+            && !compute.range
+            && !compute.needs_range_rate()
+            && !compute.needs_equatorial()
+        {
+            return Ok(LookAngles::default());
+        }
+
+        let (eci, velocity) = self.state_at(datetime)?;
+        let orientation = eop.orientation_at(datetime);
+        let gst = orientation.gst(datetime);
+        let polar_motion = orientation.polar_motion_matrix();
+        let pef = eci.to_ecef(gst);
+        let ecef = pef.rotated(&polar_motion);
+
+        let rate = compute.needs_range_rate().then(|| {
+            let velocity = velocity.to_ecef(gst, &pef).rotated(&polar_motion);
+            range_rate(observer, &ecef, &velocity)
+        });
+
+        let equatorial = compute.needs_equatorial().then(|| {
+            Equatorial::observe(
+                observer,
+                &ecef,
+                &orientation,
+                compute.equatorial_frame,
+                datetime,
+            )
+        });
+
+        let horizon = Horizon::observe(observer, ecef);
 
-        let range = if compute.range {
-            Some(Length::new::<kilometer>(range_km))
-        } else {
-            None
+        let shifted = |shift: fn(Frequency, Velocity) -> Frequency| {
+            compute
+                .nominal_frequency
+                .zip(rate)
+                .map(|(f, r)| shift(f, r))
         };
 
         Ok(LookAngles {
-            azimuth,
-            elevation,
-            range,
+            azimuth: compute.azimuth.then_some(horizon.azimuth),
+            elevation: compute.elevation.then_some(horizon.elevation),
+            apparent_elevation: compute.apparent_elevation.then(|| {
+                let standard = Atmosphere::default();
+                compute
+                    .atmosphere
+                    .as_ref()
+                    .unwrap_or(&standard)
+                    .apparent_elevation(horizon.elevation)
+            }),
+            range: compute.range.then_some(horizon.range),
+            range_rate: compute.range_rate.then_some(rate).flatten(),
+            downlink_frequency: compute
+                .downlink_frequency
+                .then(|| shifted(downlink_frequency))
+                .flatten(),
+            uplink_frequency: compute
+                .uplink_frequency
+                .then(|| shifted(uplink_frequency))
+                .flatten(),
+            right_ascension: equatorial
+                .as_ref()
+                .filter(|_| compute.right_ascension)
+                .map(|e| e.right_ascension),
+            declination: equatorial
+                .as_ref()
+                .filter(|_| compute.declination)
+                .map(|e| e.declination),
+            hour_angle: equatorial
+                .as_ref()
+                .filter(|_| compute.hour_angle)
+                .map(|e| e.hour_angle),
         })
     }
 }
@@ -1,7 +1,26 @@
+pub mod analytic;
+pub mod celestial;
 pub mod consts;
 pub mod coords;
+pub mod cowell;
+pub mod eclipses;
+pub mod element_set;
+pub mod elements;
+pub mod eop;
+pub mod illumination;
+pub mod interpolation;
 pub mod look_angles;
 pub mod models;
+pub mod moon;
+pub mod oem;
+pub mod omm;
+pub mod passes;
 pub mod position;
+pub mod precession;
 pub mod propagator;
+pub mod refraction;
+pub mod rotation;
+pub mod search;
+pub mod sun;
 pub mod time;
+pub mod visibility;
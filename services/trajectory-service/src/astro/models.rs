@@ -1,8 +1,17 @@
-use chrono::{DateTime, Utc};
-use uom::si::f64::{Angle, Length};
+use chrono::{DateTime, TimeDelta, Utc};
+use std::fmt;
+use std::fmt::Formatter;
+use uom::si::f64::{Angle, Frequency, Length, Velocity};
 
-use crate::astro::coords::{ecef::Ecef, eci::Eci, geodetic::Geodetic};
+use crate::astro::coords::{
+    ecef::{Ecef, EcefVelocity},
+    eci::{Eci, EciVelocity},
+    frames::{EarthFixedFrame, InertialFrame},
+    geodetic::Geodetic,
+};
+use crate::astro::illumination::Illumination;
 
+#[derive(Clone)]
 pub struct Tle {
     pub norad_id: u32,
     pub satellite_name: String,
@@ -15,10 +24,216 @@ pub struct SatellitePosition {
     pub eci: Option<Eci>,
     pub ecef: Option<Ecef>,
     pub geodetic: Option<Geodetic>,
+    pub eci_velocity: Option<EciVelocity>,
+    pub ecef_velocity: Option<EcefVelocity>,
+    pub illumination: Option<Illumination>,
+    pub inertial_frame: InertialFrame,
+    pub earth_fixed_frame: EarthFixedFrame,
 }
 
+#[derive(Default)]
 pub struct LookAngles {
     pub azimuth: Option<Angle>,
     pub elevation: Option<Angle>,
+    pub apparent_elevation: Option<Angle>,
     pub range: Option<Length>,
+    pub range_rate: Option<Velocity>,
+    pub downlink_frequency: Option<Frequency>,
+    pub uplink_frequency: Option<Frequency>,
+    pub right_ascension: Option<Angle>,
+    pub declination: Option<Angle>,
+    pub hour_angle: Option<Angle>,
+}
+
+pub struct CelestialBodyPosition {
+    pub eci: Option<Eci>,
+    pub ecef: Option<Ecef>,
+    pub look_angles: Option<LookAngles>,
+}
+
+pub struct TrackingSample {
+    pub datetime: DateTime<Utc>,
+    pub position: SatellitePosition,
+    pub look_angles: Option<LookAngles>,
+}
+
+pub struct Pass {
+    pub rise_time: DateTime<Utc>,
+    pub rise_azimuth: Angle,
+    pub culmination_time: DateTime<Utc>,
+    pub culmination_elevation: Angle,
+    pub culmination_range: Length,
+    pub set_time: DateTime<Utc>,
+    pub set_azimuth: Angle,
+    pub visible_segments: Vec<VisibleSegment>,
+}
+
+/// Satellite direction at a given instant of a pass.
+pub struct PassPoint {
+    pub time: DateTime<Utc>,
+    pub azimuth: Angle,
+    pub elevation: Angle,
+}
+
+/// Part of a pass during which the satellite is optically visible.
+pub struct VisibleSegment {
+    pub start: PassPoint,
+    pub max: PassPoint,
+    pub end: PassPoint,
+}
+
+/// Passage through Earth's shadow; the umbra instants are absent for penumbral-only eclipses.
+pub struct Eclipse {
+    pub penumbra_entry: DateTime<Utc>,
+    pub umbra_entry: Option<DateTime<Utc>>,
+    pub umbra_exit: Option<DateTime<Utc>>,
+    pub penumbra_exit: DateTime<Utc>,
+}
+
+/// Mean elements of a TLE, in the TEME frame of its epoch.
+pub struct MeanElements {
+    pub epoch: DateTime<Utc>,
+    pub inclination: Angle,
+    pub right_ascension: Angle,
+    pub eccentricity: f64,
+    pub argument_of_perigee: Angle,
+    pub mean_anomaly: Angle,
+    /// Kozai mean motion, in revolutions per day.
+    pub mean_motion: f64,
+    /// Drag term, in inverse Earth radii.
+    pub bstar: f64,
+}
+
+/// Orbit size and extent implied by the mean elements.
+pub struct DerivedElements {
+    pub semi_major_axis: Length,
+    pub period: TimeDelta,
+    pub apogee_altitude: Length,
+    pub perigee_altitude: Length,
+}
+
+/// Two-body elements matching a state vector, in TEME.
+pub struct OsculatingElements {
+    pub datetime: DateTime<Utc>,
+    pub semi_major_axis: Length,
+    pub eccentricity: f64,
+    pub inclination: Angle,
+    pub right_ascension: Angle,
+    pub argument_of_perigee: Angle,
+    pub true_anomaly: Angle,
+    pub mean_anomaly: Angle,
+}
+
+pub struct OrbitalElements {
+    pub mean: MeanElements,
+    pub derived: DerivedElements,
+    pub osculating: OsculatingElements,
+}
+
+/// Orbit regime of an element set, which decides how fast its predictions degrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrbitRegime {
+    /// Period below about 128 minutes (altitude below about 2000 km).
+    Leo,
+    /// Anything between LEO and GEO.
+    Meo,
+    /// Period of about one sidereal day.
+    Geo,
+    /// Eccentricity of 0.25 or more, e.g. Molniya and transfer orbits.
+    Heo,
+}
+
+impl fmt::Display for OrbitRegime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Leo => write!(f, "LEO"),
+            Self::Meo => write!(f, "MEO"),
+            Self::Geo => write!(f, "GEO"),
+            Self::Heo => write!(f, "HEO"),
+        }
+    }
+}
+
+/// Propagator a request asks for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PropagatorKind {
+    /// SGP4, or SDP4 for deep-space orbits.
+    #[default]
+    Sgp4,
+    /// Keplerian motion of the osculating orbit at the element set epoch.
+    TwoBody,
+    /// Mean elements with the secular J2 drift of the node, perigee and mean anomaly.
+    J2Secular,
+    /// Numerical integration of the state at the element set epoch.
+    Cowell,
+}
+
+/// Model a propagator runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagationModel {
+    /// Near-Earth SGP4, for periods below 225 minutes.
+    Sgp4,
+    /// Deep-space SDP4, with lunar and solar perturbations.
+    Sdp4,
+    TwoBody,
+    J2Secular,
+    /// Cowell integration with J2–J6 zonals, drag from B* and Sun/Moon third bodies.
+    Cowell,
+    /// Lagrange interpolation of a supplied ephemeris.
+    Lagrange,
+    /// Hermite interpolation of a supplied ephemeris, matching positions and velocities.
+    Hermite,
+}
+
+impl fmt::Display for PropagationModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sgp4 => write!(f, "SGP4"),
+            Self::Sdp4 => write!(f, "SDP4"),
+            Self::TwoBody => write!(f, "TWO_BODY"),
+            Self::J2Secular => write!(f, "J2_SECULAR"),
+            Self::Cowell => write!(f, "COWELL"),
+            Self::Lagrange => write!(f, "LAGRANGE"),
+            Self::Hermite => write!(f, "HERMITE"),
+        }
+    }
+}
+
+/// Geopotential resonance terms integrated by SDP4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resonance {
+    None,
+    /// Geosynchronous orbits.
+    OneDay,
+    /// 12-hour eccentric orbits such as Molniya.
+    HalfDay,
+}
+
+/// Earth gravity constants of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GravityModel {
+    Wgs84,
+    Egm96,
+}
+
+impl fmt::Display for GravityModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wgs84 => write!(f, "WGS84"),
+            Self::Egm96 => write!(f, "EGM96"),
+        }
+    }
+}
+
+/// How a propagator computes states, reported with every result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropagationMethod {
+    pub model: PropagationModel,
+    /// Set for SDP4 only.
+    pub resonance: Option<Resonance>,
+    /// Unset for interpolated ephemerides.
+    pub gravity_model: Option<GravityModel>,
+    /// AFSPC compatibility mode (original sidereal time and epoch handling) rather than
+    /// the improved mode.
+    pub afspc_compatibility_mode: bool,
 }
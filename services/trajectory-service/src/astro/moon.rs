@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use uom::si::f64::Length;
+use uom::si::length::kilometer;
+
+use crate::astro::consts::A;
+use crate::astro::coords::eci::Eci;
+use crate::astro::time::julian_centuries_tt;
+
+/// Sine of `deg + rate·t`, with both terms in degrees.
+fn sin_term(deg: f64, rate: f64, t: f64) -> f64 {
+    rate.mul_add(t, deg).to_radians().sin()
+}
+
+/// Cosine of `deg + rate·t`, with both terms in degrees.
+fn cos_term(deg: f64, rate: f64, t: f64) -> f64 {
+    rate.mul_add(t, deg).to_radians().cos()
+}
+
+/// Geocentric position of the Moon in the mean equator and equinox of date.
+///
+/// Low-precision lunar theory from the Astronomical Almanac (also Vallado,
+/// *Fundamentals of Astrodynamics and Applications*, Algorithm 31), accurate to about
+/// 0.3° in ecliptic longitude and 0.2° in latitude. The distance follows from the
+/// horizontal parallax.
+pub fn moon_eci(datetime: DateTime<Utc>) -> Eci {
+    let t = julian_centuries_tt(datetime);
+
+    let ecliptic_longitude = (218.32 + 481_267.881_3 * t + 6.29 * sin_term(134.9, 477_198.85, t)
+        - 1.27 * sin_term(259.2, -413_335.38, t)
+        + 0.66 * sin_term(235.7, 890_534.23, t)
+        + 0.21 * sin_term(269.9, 954_397.70, t)
+        - 0.19 * sin_term(357.5, 35_999.05, t)
+        - 0.11 * sin_term(186.6, 966_404.05, t))
+    .to_radians();
+
+    let ecliptic_latitude = (5.13 * sin_term(93.3, 483_202.03, t)
+        + 0.28 * sin_term(228.2, 960_400.87, t)
+        - 0.28 * sin_term(318.3, 6_003.18, t)
+        - 0.17 * sin_term(217.6, -407_332.20, t))
+    .to_radians();
+
+    let parallax = (0.9508
+        + 0.0518 * cos_term(134.9, 477_198.85, t)
+        + 0.0095 * cos_term(259.2, -413_335.38, t)
+        + 0.0078 * cos_term(235.7, 890_534.23, t)
+        + 0.0028 * cos_term(269.9, 954_397.70, t))
+    .to_radians();
+
+    let obliquity = 23.439_291_f64.to_radians() - 0.013_004_2_f64.to_radians() * t;
+
+    let distance_km = A / parallax.sin();
+
+    let (sin_lon, cos_lon) = ecliptic_longitude.sin_cos();
+    let (sin_lat, cos_lat) = ecliptic_latitude.sin_cos();
+    let (sin_eps, cos_eps) = obliquity.sin_cos();
+
+    Eci {
+        x: Length::new::<kilometer>(distance_km * cos_lat * cos_lon),
+        y: Length::new::<kilometer>(
+            distance_km * (cos_eps * cos_lat * sin_lon - sin_eps * sin_lat),
+        ),
+        z: Length::new::<kilometer>(
+            distance_km * (sin_eps * cos_lat * sin_lon + cos_eps * sin_lat),
+        ),
+    }
+}
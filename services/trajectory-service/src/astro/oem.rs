@@ -0,0 +1,180 @@
+//! CCSDS Orbit Ephemeris Messages (OEM) in the KVN text form, read into an
+//! [`Ephemeris`].
+//!
+//! Only single-segment Earth-centred messages are accepted, in TEME, EME2000 or
+//! GCRF/ICRF and on the UTC, TAI, TT or GPS time scales. Accelerations, covariance
+//! blocks and comments are ignored.
+//!
+//! See: [CCSDS 502.0-B-3, Orbit Data Messages](https://public.ccsds.org/Pubs/502x0b3e1.pdf)
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::astro::coords::frames::InertialFrame;
+use crate::astro::eop::EopTable;
+use crate::astro::interpolation::{Ephemeris, EphemerisState, InterpolationMethod};
+use crate::astro::omm::parse_ccsds_time;
+use crate::astro::time::{Clock, TimeScale};
+use crate::domain::errors::EphemerisError;
+
+/// Time scales are converted without Earth orientation data, which only UT1 needs.
+static NO_EOP: EopTable = EopTable::empty();
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    Metadata,
+    Data,
+    Covariance,
+}
+
+impl Ephemeris {
+    pub fn from_oem(document: &str) -> Result<Self, EphemerisError> {
+        let (metadata, lines) = segment(document)?;
+
+        let get = |key: &'static str| metadata.get(key).map(String::as_str);
+        let required = |key: &'static str| get(key).ok_or(EphemerisError::MissingField(key));
+
+        let center = required("CENTER_NAME")?;
+        if !center.eq_ignore_ascii_case("EARTH") {
+            return Err(EphemerisError::Unsupported(
+                "CENTER_NAME",
+                center.to_string(),
+            ));
+        }
+
+        let frame = match required("REF_FRAME")?.to_ascii_uppercase().as_str() {
+            "TEME" => InertialFrame::Teme,
+            "EME2000" | "J2000" => InertialFrame::J2000,
+            "GCRF" | "ICRF" => InertialFrame::Gcrs,
+            other => return Err(EphemerisError::Unsupported("REF_FRAME", other.to_string())),
+        };
+
+        let scale = match required("TIME_SYSTEM")?.to_ascii_uppercase().as_str() {
+            "UTC" => TimeScale::Utc,
+            "TAI" => TimeScale::Tai,
+            "TT" => TimeScale::Tt,
+            "GPS" => TimeScale::Gps,
+            other => {
+                return Err(EphemerisError::Unsupported(
+                    "TIME_SYSTEM",
+                    other.to_string(),
+                ));
+            }
+        };
+        let clock = Clock::new(scale, &NO_EOP);
+        let time = |key: &'static str, value: &str| {
+            parse_ccsds_time(value)
+                .map(|reading| clock.utc(reading))
+                .ok_or_else(|| EphemerisError::InvalidField(key, value.to_string()))
+        };
+
+        let method = match get("INTERPOLATION").map(str::to_ascii_uppercase).as_deref() {
+            None | Some("LAGRANGE") => InterpolationMethod::Lagrange,
+            Some("HERMITE") => InterpolationMethod::Hermite,
+            Some(other) => {
+                return Err(EphemerisError::Unsupported(
+                    "INTERPOLATION",
+                    other.to_string(),
+                ));
+            }
+        };
+        let degree = get("INTERPOLATION_DEGREE").map_or_else(
+            || Ok(method.default_degree()),
+            |value| {
+                value.parse().map_err(|_| {
+                    EphemerisError::InvalidField("INTERPOLATION_DEGREE", value.to_string())
+                })
+            },
+        )?;
+
+        let states = lines
+            .into_iter()
+            .map(|line| parse_state(line, time))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let usable = |key: &'static str| -> Result<Option<DateTime<Utc>>, EphemerisError> {
+            get(key).map(|value| time(key, value)).transpose()
+        };
+
+        Self::new(
+            get("OBJECT_NAME").map(str::to_string),
+            get("OBJECT_ID").and_then(|id| id.parse().ok()),
+            frame,
+            method,
+            degree,
+            states,
+        )?
+        .with_usable_span(usable("USEABLE_START_TIME")?, usable("USEABLE_STOP_TIME")?)
+    }
+}
+
+/// Metadata and ephemeris data lines of the single segment of `document`.
+fn segment(document: &str) -> Result<(HashMap<String, String>, Vec<&str>), EphemerisError> {
+    let mut section = Section::Header;
+    let mut segments = 0;
+    let mut metadata = HashMap::new();
+    let mut lines = Vec::new();
+
+    for line in document.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("COMMENT") {
+            continue;
+        }
+
+        section = match (section, line) {
+            (Section::Covariance, "COVARIANCE_STOP") | (Section::Metadata, "META_STOP") => {
+                Section::Data
+            }
+            (Section::Covariance, _) | (Section::Data, "COVARIANCE_START") => Section::Covariance,
+            (_, "META_START") => {
+                segments += 1;
+                if segments > 1 {
+                    return Err(EphemerisError::MultipleSegments);
+                }
+                Section::Metadata
+            }
+            (Section::Metadata, _) => {
+                let (key, value) = line
+                    .split_once('=')
+                    .ok_or(EphemerisError::Malformed("metadata line without '='"))?;
+                metadata.insert(key.trim().to_string(), value.trim().to_string());
+                Section::Metadata
+            }
+            (Section::Data, _) => {
+                lines.push(line);
+                Section::Data
+            }
+            // header keywords such as CCSDS_OEM_VERS and ORIGINATOR
+            (Section::Header, _) => Section::Header,
+        };
+    }
+
+    if segments == 0 {
+        return Err(EphemerisError::Malformed("no META_START"));
+    }
+
+    Ok((metadata, lines))
+}
+
+/// Epoch, position and velocity of a data line, optionally followed by an acceleration.
+fn parse_state(
+    line: &str,
+    time: impl Fn(&'static str, &str) -> Result<DateTime<Utc>, EphemerisError>,
+) -> Result<EphemerisState, EphemerisError> {
+    let invalid = || EphemerisError::InvalidField("ephemeris line", line.to_string());
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 7 && tokens.len() != 10 {
+        return Err(invalid());
+    }
+
+    let mut values = [0.0; 6];
+    for (value, token) in values.iter_mut().zip(&tokens[1..7]) {
+        *value = token.parse().map_err(|_| invalid())?;
+    }
+
+    Ok(EphemerisState {
+        epoch: time("ephemeris epoch", tokens[0])?,
+        position: [values[0], values[1], values[2]],
+        velocity: [values[3], values[4], values[5]],
+    })
+}
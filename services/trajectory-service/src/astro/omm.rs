@@ -0,0 +1,596 @@
+//! CCSDS Orbit Mean-Elements Messages (OMM) for SGP4 element sets.
+//!
+//! The three encodings of CCSDS 502.0-B share the same keywords, so every format is
+//! reduced to keyword/value pairs before being read into an [`ElementSet`]:
+//! - JSON, the flat object (or one-element array) served by the public GP catalogs,
+//! - XML, the NDM/XML `<omm>` document, of which only the leaf elements are read,
+//! - KVN, the `KEYWORD = value` text form.
+//!
+//! Only single-object messages in TEME with UTC epochs and the SGP/SGP4 theory are
+//! accepted, which is what the public catalogs publish.
+//!
+//! See: [CCSDS 502.0-B-3, Orbit Data Messages](https://public.ccsds.org/Pubs/502x0b3e1.pdf)
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::astro::element_set::ElementSet;
+use crate::domain::errors::ElementSetError;
+
+const OMM_VERSION: &str = "2.0";
+const ORIGINATOR: &str = "TRAJECTORY-SERVICE";
+const EPOCH_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OmmFormat {
+    Json,
+    Xml,
+    Kvn,
+}
+
+impl ElementSet {
+    pub fn from_omm(document: &str, format: OmmFormat) -> Result<Self, ElementSetError> {
+        let fields = match format {
+            OmmFormat::Json => json_fields(document)?,
+            OmmFormat::Xml => xml_fields(document)?,
+            OmmFormat::Kvn => kvn_fields(document),
+        };
+
+        Self::from_fields(&collect_unique(fields)?)
+    }
+
+    /// Serialize as an OMM; `created` is the creation date of the XML and KVN headers.
+    pub fn to_omm(&self, format: OmmFormat, created: DateTime<Utc>) -> String {
+        match format {
+            OmmFormat::Json => self.to_json(),
+            OmmFormat::Xml => self.to_xml(created),
+            OmmFormat::Kvn => self.to_kvn(created),
+        }
+    }
+
+    fn from_fields(fields: &HashMap<String, String>) -> Result<Self, ElementSetError> {
+        let get = |key: &'static str| fields.get(key).map(String::as_str);
+        let required = |key: &'static str| get(key).ok_or(ElementSetError::MissingField(key));
+
+        for (key, expected) in [
+            ("CENTER_NAME", &["EARTH"][..]),
+            ("REF_FRAME", &["TEME"]),
+            ("TIME_SYSTEM", &["UTC"]),
+            ("MEAN_ELEMENT_THEORY", &["SGP4", "SGP/SGP4"]),
+        ] {
+            if let Some(value) = get(key)
+                && !expected.iter().any(|e| value.eq_ignore_ascii_case(e))
+            {
+                return Err(ElementSetError::Unsupported(key, value.to_string()));
+            }
+        }
+
+        let classification = match get("CLASSIFICATION_TYPE").unwrap_or("U") {
+            "U" => sgp4::Classification::Unclassified,
+            "C" => sgp4::Classification::Classified,
+            "S" => sgp4::Classification::Secret,
+            other => {
+                return Err(ElementSetError::InvalidField(
+                    "CLASSIFICATION_TYPE",
+                    other.to_string(),
+                ));
+            }
+        };
+
+        Ok(Self {
+            object_name: get("OBJECT_NAME").map(str::to_string),
+            object_id: get("OBJECT_ID").map(str::to_string),
+            norad_id: parse("NORAD_CAT_ID", required("NORAD_CAT_ID")?)?,
+            classification,
+            epoch: parse_epoch(required("EPOCH")?)?,
+            mean_motion: parse("MEAN_MOTION", required("MEAN_MOTION")?)?,
+            eccentricity: parse("ECCENTRICITY", required("ECCENTRICITY")?)?,
+            inclination: parse("INCLINATION", required("INCLINATION")?)?,
+            right_ascension: parse("RA_OF_ASC_NODE", required("RA_OF_ASC_NODE")?)?,
+            argument_of_perigee: parse("ARG_OF_PERICENTER", required("ARG_OF_PERICENTER")?)?,
+            mean_anomaly: parse("MEAN_ANOMALY", required("MEAN_ANOMALY")?)?,
+            ephemeris_type: get("EPHEMERIS_TYPE").map_or(Ok(0), |v| parse("EPHEMERIS_TYPE", v))?,
+            element_set_number: get("ELEMENT_SET_NO")
+                .map_or(Ok(0), |v| parse("ELEMENT_SET_NO", v))?,
+            revolution_number: get("REV_AT_EPOCH").map_or(Ok(0), |v| parse("REV_AT_EPOCH", v))?,
+            bstar: parse("BSTAR", required("BSTAR")?)?,
+            mean_motion_dot: parse("MEAN_MOTION_DOT", required("MEAN_MOTION_DOT")?)?,
+            mean_motion_ddot: parse("MEAN_MOTION_DDOT", required("MEAN_MOTION_DDOT")?)?,
+        })
+    }
+
+    fn metadata_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::with_capacity(6);
+        if let Some(name) = &self.object_name {
+            fields.push(("OBJECT_NAME", name.clone()));
+        }
+        if let Some(id) = &self.object_id {
+            fields.push(("OBJECT_ID", id.clone()));
+        }
+        fields.extend([
+            ("CENTER_NAME", "EARTH".to_string()),
+            ("REF_FRAME", "TEME".to_string()),
+            ("TIME_SYSTEM", "UTC".to_string()),
+            ("MEAN_ELEMENT_THEORY", "SGP4".to_string()),
+        ]);
+        fields
+    }
+
+    fn mean_element_fields(&self) -> [(&'static str, String); 7] {
+        [
+            ("EPOCH", self.epoch.format(EPOCH_FORMAT).to_string()),
+            ("MEAN_MOTION", self.mean_motion.to_string()),
+            ("ECCENTRICITY", self.eccentricity.to_string()),
+            ("INCLINATION", self.inclination.to_string()),
+            ("RA_OF_ASC_NODE", self.right_ascension.to_string()),
+            ("ARG_OF_PERICENTER", self.argument_of_perigee.to_string()),
+            ("MEAN_ANOMALY", self.mean_anomaly.to_string()),
+        ]
+    }
+
+    fn tle_parameter_fields(&self) -> [(&'static str, String); 8] {
+        [
+            ("EPHEMERIS_TYPE", self.ephemeris_type.to_string()),
+            (
+                "CLASSIFICATION_TYPE",
+                self.classification_code().to_string(),
+            ),
+            ("NORAD_CAT_ID", self.norad_id.to_string()),
+            ("ELEMENT_SET_NO", self.element_set_number.to_string()),
+            ("REV_AT_EPOCH", self.revolution_number.to_string()),
+            ("BSTAR", self.bstar.to_string()),
+            ("MEAN_MOTION_DOT", self.mean_motion_dot.to_string()),
+            ("MEAN_MOTION_DDOT", self.mean_motion_ddot.to_string()),
+        ]
+    }
+
+    /// Flat object with the keys and value types of the public GP catalogs.
+    fn to_json(&self) -> String {
+        let mut object = Vec::with_capacity(17);
+        if let Some(name) = &self.object_name {
+            object.push(("OBJECT_NAME", serde_json::Value::from(name.as_str())));
+        }
+        if let Some(id) = &self.object_id {
+            object.push(("OBJECT_ID", serde_json::Value::from(id.as_str())));
+        }
+
+        object.extend([
+            (
+                "EPOCH",
+                serde_json::Value::from(self.epoch.format(EPOCH_FORMAT).to_string()),
+            ),
+            ("MEAN_MOTION", serde_json::Value::from(self.mean_motion)),
+            ("ECCENTRICITY", serde_json::Value::from(self.eccentricity)),
+            ("INCLINATION", serde_json::Value::from(self.inclination)),
+            (
+                "RA_OF_ASC_NODE",
+                serde_json::Value::from(self.right_ascension),
+            ),
+            (
+                "ARG_OF_PERICENTER",
+                serde_json::Value::from(self.argument_of_perigee),
+            ),
+            ("MEAN_ANOMALY", serde_json::Value::from(self.mean_anomaly)),
+            (
+                "EPHEMERIS_TYPE",
+                serde_json::Value::from(self.ephemeris_type),
+            ),
+            (
+                "CLASSIFICATION_TYPE",
+                serde_json::Value::from(self.classification_code().to_string()),
+            ),
+            ("NORAD_CAT_ID", serde_json::Value::from(self.norad_id)),
+            (
+                "ELEMENT_SET_NO",
+                serde_json::Value::from(self.element_set_number),
+            ),
+            (
+                "REV_AT_EPOCH",
+                serde_json::Value::from(self.revolution_number),
+            ),
+            ("BSTAR", serde_json::Value::from(self.bstar)),
+            (
+                "MEAN_MOTION_DOT",
+                serde_json::Value::from(self.mean_motion_dot),
+            ),
+            (
+                "MEAN_MOTION_DDOT",
+                serde_json::Value::from(self.mean_motion_ddot),
+            ),
+        ]);
+
+        // written by hand to keep the keys in the usual order
+        let members: Vec<String> = object
+            .into_iter()
+            .map(|(key, value)| format!("\"{key}\":{value}"))
+            .collect();
+        format!("{{{}}}", members.join(","))
+    }
+
+    fn to_xml(&self, created: DateTime<Utc>) -> String {
+        let leaves = |fields: &[(&'static str, String)], indent: &str| {
+            fields.iter().fold(String::new(), |mut xml, (key, value)| {
+                // writing into a String cannot fail
+                let _ = writeln!(xml, "{indent}<{key}>{}</{key}>", xml_escape(value));
+                xml
+            })
+        };
+
+        format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<ndm xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ",
+                "xsi:noNamespaceSchemaLocation=",
+                "\"https://sanaregistry.org/r/ndmxml_unqualified/ndmxml-2.0.0-master-2.0.xsd\">\n",
+                "  <omm id=\"CCSDS_OMM_VERS\" version=\"{version}\">\n",
+                "    <header>\n{header}    </header>\n",
+                "    <body>\n",
+                "      <segment>\n",
+                "        <metadata>\n{metadata}        </metadata>\n",
+                "        <data>\n",
+                "          <meanElements>\n{mean_elements}          </meanElements>\n",
+                "          <tleParameters>\n{tle_parameters}          </tleParameters>\n",
+                "        </data>\n",
+                "      </segment>\n",
+                "    </body>\n",
+                "  </omm>\n",
+                "</ndm>\n",
+            ),
+            version = OMM_VERSION,
+            header = leaves(&header_fields(created), "      "),
+            metadata = leaves(&self.metadata_fields(), "          "),
+            mean_elements = leaves(&self.mean_element_fields(), "            "),
+            tle_parameters = leaves(&self.tle_parameter_fields(), "            "),
+        )
+    }
+
+    fn to_kvn(&self, created: DateTime<Utc>) -> String {
+        std::iter::once(("CCSDS_OMM_VERS", OMM_VERSION.to_string()))
+            .chain(header_fields(created))
+            .chain(self.metadata_fields())
+            .chain(self.mean_element_fields())
+            .chain(self.tle_parameter_fields())
+            .fold(String::new(), |mut kvn, (key, value)| {
+                let _ = writeln!(kvn, "{key:<19} = {value}");
+                kvn
+            })
+    }
+}
+
+fn header_fields(created: DateTime<Utc>) -> [(&'static str, String); 2] {
+    [
+        ("CREATION_DATE", created.format(EPOCH_FORMAT).to_string()),
+        ("ORIGINATOR", ORIGINATOR.to_string()),
+    ]
+}
+
+fn parse<T: std::str::FromStr>(key: &'static str, value: &str) -> Result<T, ElementSetError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ElementSetError::InvalidField(key, value.to_string()))
+}
+
+fn parse_epoch(value: &str) -> Result<DateTime<Utc>, ElementSetError> {
+    parse_ccsds_time(value).ok_or_else(|| ElementSetError::InvalidField("EPOCH", value.to_string()))
+}
+
+/// Calendar (`2020-07-12T21:16:01.000416`) or day-of-year (`2020-194T21:16:01`) time,
+/// shared by every CCSDS navigation data message.
+pub fn parse_ccsds_time(value: &str) -> Option<DateTime<Utc>> {
+    let trimmed = value.trim().trim_end_matches('Z');
+
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%jT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+        .map(|datetime| datetime.and_utc())
+}
+
+/// Keywords must appear once, a repeated one means several objects were sent.
+fn collect_unique(
+    fields: Vec<(String, String)>,
+) -> Result<HashMap<String, String>, ElementSetError> {
+    let mut unique = HashMap::with_capacity(fields.len());
+    for (key, value) in fields {
+        if unique.contains_key(&key) {
+            return Err(ElementSetError::MultipleObjects(key));
+        }
+        unique.insert(key, value);
+    }
+    Ok(unique)
+}
+
+fn json_fields(document: &str) -> Result<Vec<(String, String)>, ElementSetError> {
+    let object = match serde_json::from_str(document)? {
+        serde_json::Value::Object(object) => object,
+        serde_json::Value::Array(mut objects) if objects.len() == 1 => match objects.pop() {
+            Some(serde_json::Value::Object(object)) => object,
+            _ => return Err(ElementSetError::Malformed("expected an OMM object")),
+        },
+        serde_json::Value::Array(objects) if objects.len() > 1 => {
+            return Err(ElementSetError::MultipleObjects(
+                "the JSON array".to_string(),
+            ));
+        }
+        _ => return Err(ElementSetError::Malformed("expected an OMM object")),
+    };
+
+    Ok(object
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(text) => text,
+                serde_json::Value::Number(number) => number.to_string(),
+                _ => return None,
+            };
+            Some((key, value))
+        })
+        .collect())
+}
+
+/// Text of every leaf element, ignoring the document structure.
+///
+/// Comments and user-defined parameters, which may repeat, are left out; several
+/// `<omm>` or `<segment>` elements mean several objects were sent.
+fn xml_fields(document: &str) -> Result<Vec<(String, String)>, ElementSetError> {
+    let unterminated = || ElementSetError::Malformed("unterminated XML tag");
+    let mut fields = Vec::new();
+    let mut messages = 0;
+    let mut segments = 0;
+    let mut rest = document;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+
+        if let Some(comment) = rest.strip_prefix("!--") {
+            let end = comment.find("-->").ok_or_else(unterminated)?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+
+        let end = rest.find('>').ok_or_else(unterminated)?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with(['?', '!', '/']) || tag.ends_with('/') {
+            continue;
+        }
+
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        match name {
+            "omm" => messages += 1,
+            "segment" => segments += 1,
+            _ => {}
+        }
+        if messages > 1 || segments > 1 {
+            return Err(ElementSetError::MultipleObjects(
+                "the XML document".to_string(),
+            ));
+        }
+        if matches!(name, "COMMENT" | "USER_DEFINED") {
+            continue;
+        }
+
+        let Some(text_end) = rest.find('<') else {
+            break;
+        };
+
+        if rest[text_end + 1..]
+            .strip_prefix('/')
+            .and_then(|closing| closing.strip_prefix(name))
+            .is_some_and(|closing| closing.trim_start().starts_with('>'))
+        {
+            fields.push((name.to_string(), xml_unescape(rest[..text_end].trim())));
+        }
+    }
+
+    if fields.is_empty() {
+        return Err(ElementSetError::Malformed(
+            "no OMM elements in the XML document",
+        ));
+    }
+
+    Ok(fields)
+}
+
+/// `KEYWORD = value` lines; comments, blank lines and units in brackets are skipped.
+fn kvn_fields(document: &str) -> Vec<(String, String)> {
+    document
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("COMMENT"))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value
+                .split_once('[')
+                .map_or(value, |(value, _unit)| value)
+                .trim();
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// TLE the samples below were published alongside.
+    const ISS_LINES: (&str, &str) = (
+        "1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992",
+        "2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008",
+    );
+
+    /// ISS OMM as served by `celestrak.org/NORAD/elements/gp.php` with `FORMAT=json`.
+    const CELESTRAK_JSON: &str = r#"[{
+    "OBJECT_NAME": "ISS (ZARYA)",
+    "OBJECT_ID": "1998-067A",
+    "EPOCH": "2020-07-12T21:16:01.000416",
+    "MEAN_MOTION": 15.49507896,
+    "ECCENTRICITY": 0.0001413,
+    "INCLINATION": 51.6461,
+    "RA_OF_ASC_NODE": 221.2784,
+    "ARG_OF_PERICENTER": 89.1723,
+    "MEAN_ANOMALY": 280.4612,
+    "EPHEMERIS_TYPE": 0,
+    "CLASSIFICATION_TYPE": "U",
+    "NORAD_CAT_ID": 25544,
+    "ELEMENT_SET_NO": 999,
+    "REV_AT_EPOCH": 23600,
+    "BSTAR": -3.1515e-5,
+    "MEAN_MOTION_DOT": -2.218e-5,
+    "MEAN_MOTION_DDOT": 0
+}]"#;
+
+    /// The same with `FORMAT=xml`, whose header elements are empty.
+    const CELESTRAK_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="https://sanaregistry.org/r/ndmxml_unqualified/ndmxml-2.0.0-master-2.0.xsd"><omm id="CCSDS_OMM_VERS" version="2.0"><header><CREATION_DATE/><ORIGINATOR/></header><body><segment><metadata><OBJECT_NAME>ISS (ZARYA)</OBJECT_NAME><OBJECT_ID>1998-067A</OBJECT_ID><CENTER_NAME>EARTH</CENTER_NAME><REF_FRAME>TEME</REF_FRAME><TIME_SYSTEM>UTC</TIME_SYSTEM><MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY></metadata><data><meanElements><EPOCH>2020-07-12T21:16:01.000416</EPOCH><MEAN_MOTION>15.49507896</MEAN_MOTION><ECCENTRICITY>.0001413</ECCENTRICITY><INCLINATION>51.6461</INCLINATION><RA_OF_ASC_NODE>221.2784</RA_OF_ASC_NODE><ARG_OF_PERICENTER>89.1723</ARG_OF_PERICENTER><MEAN_ANOMALY>280.4612</MEAN_ANOMALY></meanElements><tleParameters><EPHEMERIS_TYPE>0</EPHEMERIS_TYPE><CLASSIFICATION_TYPE>U</CLASSIFICATION_TYPE><NORAD_CAT_ID>25544</NORAD_CAT_ID><ELEMENT_SET_NO>999</ELEMENT_SET_NO><REV_AT_EPOCH>23600</REV_AT_EPOCH><BSTAR>-.31515E-4</BSTAR><MEAN_MOTION_DOT>-.2218E-4</MEAN_MOTION_DOT><MEAN_MOTION_DDOT>0</MEAN_MOTION_DDOT></tleParameters></data></segment></body></omm>
+</ndm>
+"#;
+
+    /// The same with `FORMAT=kvn`.
+    const CELESTRAK_KVN: &str = "\
+CCSDS_OMM_VERS = 2.0
+CREATION_DATE = 2020-07-13T03:46:12
+ORIGINATOR = 18 SPCS
+OBJECT_NAME = ISS (ZARYA)
+OBJECT_ID = 1998-067A
+CENTER_NAME = EARTH
+REF_FRAME = TEME
+TIME_SYSTEM = UTC
+MEAN_ELEMENT_THEORY = SGP4
+EPOCH = 2020-07-12T21:16:01.000416
+MEAN_MOTION = 15.49507896
+ECCENTRICITY = .0001413
+INCLINATION = 51.6461
+RA_OF_ASC_NODE = 221.2784
+ARG_OF_PERICENTER = 89.1723
+MEAN_ANOMALY = 280.4612
+EPHEMERIS_TYPE = 0
+CLASSIFICATION_TYPE = U
+NORAD_CAT_ID = 25544
+ELEMENT_SET_NO = 999
+REV_AT_EPOCH = 23600
+BSTAR = -.31515E-4
+MEAN_MOTION_DOT = -.2218E-4
+MEAN_MOTION_DDOT = 0
+";
+
+    fn samples() -> [(OmmFormat, &'static str); 3] {
+        [
+            (OmmFormat::Json, CELESTRAK_JSON),
+            (OmmFormat::Xml, CELESTRAK_XML),
+            (OmmFormat::Kvn, CELESTRAK_KVN),
+        ]
+    }
+
+    fn tle_lines(element_set: &ElementSet) -> (String, String) {
+        element_set.to_tle().unwrap()
+    }
+
+    #[test]
+    fn parses_celestrak_samples_as_the_published_tle() {
+        for (format, document) in samples() {
+            let element_set = ElementSet::from_omm(document, format).unwrap();
+
+            assert_eq!(element_set.object_name.as_deref(), Some("ISS (ZARYA)"));
+            assert_eq!(
+                tle_lines(&element_set),
+                (ISS_LINES.0.to_string(), ISS_LINES.1.to_string()),
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn omm_round_trip_keeps_the_elements() {
+        let created = parse_ccsds_time("2020-07-13T03:46:12").unwrap();
+
+        for (format, document) in samples() {
+            let element_set = ElementSet::from_omm(document, format).unwrap();
+            let written = element_set.to_omm(format, created);
+            let read = ElementSet::from_omm(&written, format).unwrap();
+
+            assert_eq!(read.object_name, element_set.object_name, "{format:?}");
+            assert_eq!(read.object_id, element_set.object_id, "{format:?}");
+            assert_eq!(read.epoch, element_set.epoch, "{format:?}");
+            assert_eq!(tle_lines(&read), tle_lines(&element_set), "{format:?}");
+        }
+    }
+
+    /// ISS OMM as served by the Space-Track `gp` class in XML.
+    const SPACE_TRACK_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="https://sanaregistry.org/r/ndmxml_unqualified/ndmxml-2.0.0-master-2.0.xsd">
+<omm id="CCSDS_OMM_VERS" version="2.0">
+<header><COMMENT>GENERATED VIA SPACE-TRACK.ORG API</COMMENT><CREATION_DATE>2020-07-13T03:46:12</CREATION_DATE><ORIGINATOR>18 SPCS</ORIGINATOR></header>
+<body>
+<segment>
+<metadata><OBJECT_NAME>ISS (ZARYA)</OBJECT_NAME><OBJECT_ID>1998-067A</OBJECT_ID><CENTER_NAME>EARTH</CENTER_NAME><REF_FRAME>TEME</REF_FRAME><TIME_SYSTEM>UTC</TIME_SYSTEM><MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY></metadata>
+<data>
+<COMMENT>This is a test comment</COMMENT>
+<meanElements><EPOCH>2020-07-12T21:16:01.000416</EPOCH><MEAN_MOTION>15.49507896</MEAN_MOTION><ECCENTRICITY>.0001413</ECCENTRICITY><INCLINATION>51.6461</INCLINATION><RA_OF_ASC_NODE>221.2784</RA_OF_ASC_NODE><ARG_OF_PERICENTER>89.1723</ARG_OF_PERICENTER><MEAN_ANOMALY>280.4612</MEAN_ANOMALY></meanElements>
+<tleParameters><EPHEMERIS_TYPE>0</EPHEMERIS_TYPE><CLASSIFICATION_TYPE>U</CLASSIFICATION_TYPE><NORAD_CAT_ID>25544</NORAD_CAT_ID><ELEMENT_SET_NO>999</ELEMENT_SET_NO><REV_AT_EPOCH>23600</REV_AT_EPOCH><BSTAR>-.31515E-4</BSTAR><MEAN_MOTION_DOT>-.00002218</MEAN_MOTION_DOT><MEAN_MOTION_DDOT>0</MEAN_MOTION_DDOT></tleParameters>
+<userDefinedParameters>
+<USER_DEFINED parameter="SEMIMAJOR_AXIS">6797.017</USER_DEFINED>
+<USER_DEFINED parameter="PERIOD">92.932</USER_DEFINED>
+<USER_DEFINED parameter="APOAPSIS">419.842</USER_DEFINED>
+<USER_DEFINED parameter="PERIAPSIS">417.921</USER_DEFINED>
+<USER_DEFINED parameter="OBJECT_TYPE">PAYLOAD</USER_DEFINED>
+<USER_DEFINED parameter="RCS_SIZE">LARGE</USER_DEFINED>
+<USER_DEFINED parameter="COUNTRY_CODE">ISS</USER_DEFINED>
+<USER_DEFINED parameter="LAUNCH_DATE">1998-11-20</USER_DEFINED>
+<USER_DEFINED parameter="SITE">TTMTR</USER_DEFINED>
+<USER_DEFINED parameter="FILE">2790393</USER_DEFINED>
+<USER_DEFINED parameter="GP_ID">158010361</USER_DEFINED>
+</userDefinedParameters>
+</data>
+</segment>
+</body>
+</omm>
+</ndm>
+"#;
+
+    #[test]
+    fn parses_space_track_xml() {
+        let element_set = ElementSet::from_omm(SPACE_TRACK_XML, OmmFormat::Xml).unwrap();
+
+        assert_eq!(element_set.object_name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(element_set.object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(element_set.norad_id, 25544);
+        assert_eq!(
+            element_set.epoch,
+            parse_ccsds_time("2020-07-12T21:16:01.000416").unwrap()
+        );
+        assert!((element_set.eccentricity - 0.000_141_3).abs() < 1e-12);
+        assert!((element_set.bstar + 3.1515e-5).abs() < 1e-12);
+        assert_eq!(element_set.element_set_number, 999);
+        assert_eq!(element_set.revolution_number, 23600);
+    }
+
+    #[test]
+    fn rejects_xml_with_several_segments() {
+        let segment_start = SPACE_TRACK_XML.find("<segment>").unwrap();
+        let segment_end = SPACE_TRACK_XML.find("</segment>").unwrap() + "</segment>".len();
+        let segment = &SPACE_TRACK_XML[segment_start..segment_end];
+        let document = SPACE_TRACK_XML.replace(segment, &format!("{segment}\n{segment}"));
+
+        assert!(matches!(
+            ElementSet::from_omm(&document, OmmFormat::Xml),
+            Err(ElementSetError::MultipleObjects(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use chrono::{DateTime, Utc};
+use uom::si::angle::radian;
+use uom::si::f64::Angle;
+
+use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
+use crate::astro::models::Pass;
+use crate::astro::propagator::Propagator;
+use crate::astro::refraction::Atmosphere;
+use crate::astro::search::{find_maximum, find_root};
+use crate::astro::time::{add_seconds, seconds_between};
+use crate::domain::errors::PropagationError;
+
+/// Step of the coarse elevation scan, in seconds.
+///
+/// Short enough that the elevation profile of a LEO pass has a single maximum
+/// between two neighbouring samples.
+const SCAN_STEP_SECONDS: f64 = 60.0;
+
+/// Resolution of the refined rise, culmination and set instants, in seconds.
+const TIME_TOLERANCE_SECONDS: f64 = 1e-3;
+
+/// Conditions a pass has to meet to be reported.
+pub struct PassCriteria {
+    pub min_elevation: Angle,
+    /// Only keep optically visible passes, with the Sun below this elevation.
+    pub max_sun_elevation: Option<Angle>,
+    /// Apply refraction, so that all elevations are apparent ones.
+    pub atmosphere: Option<Atmosphere>,
+}
+
+/// Pass boundaries in seconds since the start of the search window.
+struct PassWindow {
+    rise: f64,
+    culmination: f64,
+    culmination_elevation: f64,
+    set: f64,
+}
+
+impl Propagator {
+    /// Predict passes of the satellite over `observer` between `start` and `end`.
+    ///
+    /// The window is first scanned at a coarse step to bracket elevation maxima,
+    /// which are then refined with golden-section search. Horizon crossings around
+    /// each culmination above `min_elevation` are located with Brent's method.
+    ///
+    /// Passes already in progress at `start` (or still in progress at `end`)
+    /// are clipped to the window.
+    ///
+    /// With an `atmosphere`, elevations are apparent ones, so `min_elevation` and the
+    /// reported rise and set times account for refraction.
+    pub fn passes_between(
+        &self,
+        observer: &Geodetic,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_elevation: Angle,
+        atmosphere: Option<&Atmosphere>,
+        eop: &EopTable,
+    ) -> Result<Vec<Pass>, PropagationError> {
+        let threshold = min_elevation.get::<radian>();
+        let duration = seconds_between(start, end);
+
+        let elevation = |t: f64| -> Result<f64, PropagationError> {
+            let horizon =
+                self.observed_horizon_at(add_seconds(start, t), observer, atmosphere, eop)?;
+            Ok(horizon.elevation.get::<radian>())
+        };
+        let above_threshold = |t: f64| elevation(t).map(|el| el - threshold);
+
+        let samples = std::iter::successors(Some(0.0), |t| Some(t + SCAN_STEP_SECONDS))
+            .take_while(|&t| t < duration)
+            .chain(std::iter::once(duration))
+            .map(|t| elevation(t).map(|el| (t, el)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut windows: Vec<PassWindow> = Vec::new();
+
+        for (i, &(t, el)) in samples.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|j| samples[j]);
+            let next = samples.get(i + 1).copied();
+
+            // local maximum of the sampled profile, window edges included
+            let is_peak = prev.is_none_or(|(_, p)| p <= el) && next.is_none_or(|(_, n)| n < el);
+            if !is_peak {
+                continue;
+            }
+
+            let lo = prev.map_or(t, |(pt, _)| pt);
+            let hi = next.map_or(t, |(nt, _)| nt);
+            let (culmination, culmination_elevation) =
+                match find_maximum(elevation, lo, hi, TIME_TOLERANCE_SECONDS)? {
+                    (_, refined) if refined < el => (t, el),
+                    refined => refined,
+                };
+
+            if culmination_elevation < threshold {
+                continue;
+            }
+
+            // a second maximum within the same pass only moves the culmination
+            if let Some(last) = windows.last_mut()
+                && culmination <= last.set
+            {
+                if culmination_elevation > last.culmination_elevation {
+                    last.culmination = culmination;
+                    last.culmination_elevation = culmination_elevation;
+                }
+                continue;
+            }
+
+            // last sample below the threshold before the culmination
+            let rise = match samples[..=i]
+                .iter()
+                .rposition(|&(st, sel)| st < culmination && sel < threshold)
+            {
+                Some(j) => {
+                    let upper = samples[j + 1].0.min(culmination);
+                    find_root(above_threshold, samples[j].0, upper, TIME_TOLERANCE_SECONDS)?
+                }
+                None => 0.0,
+            };
+
+            // first sample below the threshold after the culmination
+            let set = match samples[i..]
+                .iter()
+                .position(|&(st, sel)| st > culmination && sel < threshold)
+            {
+                Some(k) => {
+                    let lower = samples[i + k - 1].0.max(culmination);
+                    find_root(
+                        above_threshold,
+                        lower,
+                        samples[i + k].0,
+                        TIME_TOLERANCE_SECONDS,
+                    )?
+                }
+                None => duration,
+            };
+
+            windows.push(PassWindow {
+                rise,
+                culmination,
+                culmination_elevation,
+                set,
+            });
+        }
+
+        windows
+            .into_iter()
+            .map(|window| {
+                let rise_time = add_seconds(start, window.rise);
+                let culmination_time = add_seconds(start, window.culmination);
+                let set_time = add_seconds(start, window.set);
+
+                let rise = self.observed_horizon_at(rise_time, observer, atmosphere, eop)?;
+                let culmination =
+                    self.observed_horizon_at(culmination_time, observer, atmosphere, eop)?;
+                let set = self.observed_horizon_at(set_time, observer, atmosphere, eop)?;
+
+                Ok(Pass {
+                    rise_time,
+                    rise_azimuth: rise.azimuth,
+                    culmination_time,
+                    culmination_elevation: culmination.elevation,
+                    culmination_range: culmination.range,
+                    set_time,
+                    set_azimuth: set.azimuth,
+                    visible_segments: Vec::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use uom::si::angle::degree;
+    use uom::si::f64::Length;
+    use uom::si::length::meter;
+
+    use crate::astro::models::{PropagatorKind, Tle};
+
+    fn iss() -> Propagator {
+        let tle = Tle {
+            norad_id: 25544,
+            satellite_name: "ISS (ZARYA)".to_string(),
+            line1: "1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992"
+                .to_string(),
+            line2: "2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008"
+                .to_string(),
+            epoch: DateTime::parse_from_rfc3339("2020-07-12T21:16:01Z")
+                .unwrap()
+                .to_utc(),
+        };
+        Propagator::from_tle(&tle, PropagatorKind::Sgp4).unwrap()
+    }
+
+    fn observer() -> Geodetic {
+        // Darmstadt
+        Geodetic {
+            lat: Angle::new::<degree>(49.87),
+            lon: Angle::new::<degree>(8.65),
+            alt: Length::new::<meter>(150.0),
+        }
+    }
+
+    fn passes(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<Pass> {
+        iss()
+            .passes_between(
+                &observer(),
+                start,
+                end,
+                Angle::new::<degree>(10.0),
+                None,
+                &EopTable::empty(),
+            )
+            .unwrap()
+    }
+
+    fn assert_close(actual: DateTime<Utc>, expected: DateTime<Utc>, what: &str) {
+        assert!(
+            (actual - expected).abs() < TimeDelta::milliseconds(10),
+            "{what} at {actual}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn clips_passes_to_the_window() {
+        let day_start = DateTime::parse_from_rfc3339("2020-07-13T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let day = passes(day_start, day_start + TimeDelta::days(1));
+        assert!(!day.is_empty());
+
+        for pass in &day {
+            assert!(pass.rise_time < pass.culmination_time);
+            assert!(pass.culmination_time < pass.set_time);
+            assert!(pass.culmination_elevation.get::<degree>() >= 10.0);
+        }
+
+        let pass = &day[0];
+        let before_culmination = pass.rise_time + (pass.culmination_time - pass.rise_time) / 2;
+        let after_culmination = pass.culmination_time + (pass.set_time - pass.culmination_time) / 2;
+
+        // in progress at the start of the window
+        let clipped = passes(before_culmination, pass.set_time + TimeDelta::minutes(1));
+        assert_eq!(clipped.len(), 1);
+        assert_eq!(clipped[0].rise_time, before_culmination);
+        assert_close(
+            clipped[0].culmination_time,
+            pass.culmination_time,
+            "culmination",
+        );
+        assert_close(clipped[0].set_time, pass.set_time, "set");
+
+        // past its culmination at the start of the window
+        let clipped = passes(after_culmination, pass.set_time + TimeDelta::minutes(1));
+        assert_eq!(clipped.len(), 1);
+        assert_eq!(clipped[0].rise_time, after_culmination);
+        assert_close(
+            clipped[0].culmination_time,
+            after_culmination,
+            "culmination",
+        );
+        assert_close(clipped[0].set_time, pass.set_time, "set");
+
+        // still in progress at the end of the window
+        let clipped = passes(pass.rise_time - TimeDelta::minutes(1), after_culmination);
+        assert_eq!(clipped.len(), 1);
+        assert_close(clipped[0].rise_time, pass.rise_time, "rise");
+        assert_close(
+            clipped[0].culmination_time,
+            pass.culmination_time,
+            "culmination",
+        );
+        assert_eq!(clipped[0].set_time, after_culmination);
+    }
+}
@@ -1,16 +1,27 @@
 use chrono::{DateTime, Utc};
 
-use crate::astro;
+use crate::astro::coords::frames::{EarthFixedFrame, InertialFrame};
 use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
+use crate::astro::illumination::Shadow;
 use crate::astro::models::SatellitePosition;
 use crate::astro::propagator::Propagator;
+use crate::astro::sun::sun_eci;
 use crate::domain::errors::PropagationError;
 
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct PositionComputation {
     pub eci: bool,
     pub ecef: bool,
     pub geodetic: bool,
+    pub eci_velocity: bool,
+    pub ecef_velocity: bool,
+    pub illumination: bool,
+    /// Frame of the inertial position and velocity.
+    pub inertial_frame: InertialFrame,
+    /// Frame of the Earth-fixed position, velocity and of the geodetic coordinates.
+    pub earth_fixed_frame: EarthFixedFrame,
 }
 
 impl Propagator {
@@ -18,22 +29,29 @@ impl Propagator {
         &self,
         datetime: DateTime<Utc>,
         compute: &PositionComputation,
+        eop: &EopTable,
     ) -> Result<SatellitePosition, PropagationError> {
-        // compute eci only if any dependent coordinate is needed
-        let eci = if compute.eci || compute.ecef || compute.geodetic {
-            Some(self.eci_at(datetime)?)
+        let needs_ecef = compute.ecef || compute.geodetic || compute.ecef_velocity;
+
+        // compute the state vector only if any dependent coordinate is needed
+        let state = if needs_ecef || compute.eci || compute.eci_velocity || compute.illumination {
+            Some(self.state_at(datetime)?)
         } else {
             None
         };
 
+        let orientation = eop.orientation_at(datetime);
+        let gst = needs_ecef.then(|| orientation.gst(datetime));
+        let earth_fixed = compute
+            .earth_fixed_frame
+            .rotation_from_pef(orientation.polar_motion);
+
         // only if ecef is requested and eci is available
-        let ecef = match (compute.ecef || compute.geodetic, &eci) {
-            (true, Some(eci_val)) => {
-                let gst = astro::time::utc_to_gst(datetime);
-                Some(eci_val.to_ecef(gst))
-            }
+        let pef = match (gst, &state) {
+            (Some(gst), Some((eci, _))) => Some(eci.to_ecef(gst)),
             _ => None,
         };
+        let ecef = pef.as_ref().map(|pef| pef.rotated(&earth_fixed));
 
         // only if geodetic is requested and ecef is available
         let geodetic = match (compute.geodetic, &ecef) {
@@ -41,10 +59,36 @@ impl Propagator {
             _ => None,
         };
 
+        // ecef velocity needs the ecef position for the earth rotation term
+        let ecef_velocity = match (compute.ecef_velocity, gst, &state, &pef) {
+            (true, Some(gst), Some((_, velocity)), Some(pef)) => {
+                Some(velocity.to_ecef(gst, pef).rotated(&earth_fixed))
+            }
+            _ => None,
+        };
+
+        let illumination = match (compute.illumination, &state) {
+            (true, Some((eci, _))) => Some(Shadow::between(eci, &sun_eci(datetime)).illumination()),
+            _ => None,
+        };
+
+        let inertial = compute.inertial_frame.rotation_from_teme(datetime);
+        let (eci, eci_velocity) = state.map_or((None, None), |(eci, velocity)| {
+            (
+                Some(eci.rotated(&inertial)),
+                Some(velocity.rotated(&inertial)),
+            )
+        });
+
         Ok(SatellitePosition {
             eci: compute.eci.then_some(eci).flatten(),
             ecef: compute.ecef.then_some(ecef).flatten(),
             geodetic,
+            eci_velocity: compute.eci_velocity.then_some(eci_velocity).flatten(),
+            ecef_velocity,
+            illumination,
+            inertial_frame: compute.inertial_frame,
+            earth_fixed_frame: compute.earth_fixed_frame,
         })
     }
 }
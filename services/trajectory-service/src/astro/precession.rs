@@ -0,0 +1,155 @@
+//! IAU-1976 precession and IAU-1980 nutation.
+//!
+//! These are the models SGP4's TEME frame is defined against, so they are used to
+//! relate TEME to the J2000 mean equator and equinox. References: Vallado,
+//! *Fundamentals of Astrodynamics and Applications*, §3.7, and Meeus,
+//! *Astronomical Algorithms*, Ch. 21–22.
+
+use chrono::{DateTime, Utc};
+
+use crate::astro::consts::ARCSEC_TO_RAD;
+use crate::astro::rotation::{Matrix3, multiply, rot_x, rot_y, rot_z, transpose};
+use crate::astro::time::julian_centuries_tt;
+
+/// Leading terms of the IAU-1980 nutation series (Meeus, Table 22.A).
+///
+/// Each row holds the multipliers of D, M, M′, F and Ω, followed by the sine
+/// coefficients of Δψ and the cosine coefficients of Δε (constant and per Julian
+/// century), in units of 0.0001″. Terms below 0.001″ are omitted, which keeps the
+/// truncation error at a few milliarcseconds.
+#[rustfmt::skip]
+const NUTATION_TERMS: [([f64; 5], f64, f64, f64, f64); 35] = [
+    ([ 0.0,  0.0,  0.0, 0.0, 1.0], -171_996.0, -174.2, 92_025.0,  8.9),
+    ([-2.0,  0.0,  0.0, 2.0, 2.0],  -13_187.0,   -1.6,  5_736.0, -3.1),
+    ([ 0.0,  0.0,  0.0, 2.0, 2.0],   -2_274.0,   -0.2,    977.0, -0.5),
+    ([ 0.0,  0.0,  0.0, 0.0, 2.0],    2_062.0,    0.2,   -895.0,  0.5),
+    ([ 0.0,  1.0,  0.0, 0.0, 0.0],    1_426.0,   -3.4,     54.0, -0.1),
+    ([ 0.0,  0.0,  1.0, 0.0, 0.0],      712.0,    0.1,     -7.0,  0.0),
+    ([-2.0,  1.0,  0.0, 2.0, 2.0],     -517.0,    1.2,    224.0, -0.6),
+    ([ 0.0,  0.0,  0.0, 2.0, 1.0],     -386.0,   -0.4,    200.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 2.0],     -301.0,    0.0,    129.0, -0.1),
+    ([-2.0, -1.0,  0.0, 2.0, 2.0],      217.0,   -0.5,    -95.0,  0.3),
+    ([-2.0,  0.0,  1.0, 0.0, 0.0],     -158.0,    0.0,      0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 1.0],      129.0,    0.1,    -70.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 2.0],      123.0,    0.0,    -53.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 0.0, 0.0],       63.0,    0.0,      0.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 0.0, 1.0],       63.0,    0.1,    -33.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 2.0, 2.0],      -59.0,    0.0,     26.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 0.0, 1.0],      -58.0,   -0.1,     32.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 1.0],      -51.0,    0.0,     27.0,  0.0),
+    ([-2.0,  0.0,  2.0, 0.0, 0.0],       48.0,    0.0,      0.0,  0.0),
+    ([ 0.0,  0.0, -2.0, 2.0, 1.0],       46.0,    0.0,    -24.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 2.0, 2.0],      -38.0,    0.0,     16.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 2.0, 2.0],      -31.0,    0.0,     13.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 0.0, 0.0],       29.0,    0.0,      0.0,  0.0),
+    ([-2.0,  0.0,  1.0, 2.0, 2.0],       29.0,    0.0,    -12.0,  0.0),
+    ([ 0.0,  0.0,  0.0, 2.0, 0.0],       26.0,    0.0,      0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 0.0],      -22.0,    0.0,      0.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 1.0],       21.0,    0.0,    -10.0,  0.0),
+    ([ 0.0,  2.0,  0.0, 0.0, 0.0],       17.0,   -0.1,      0.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 0.0, 1.0],       16.0,    0.0,     -8.0,  0.0),
+    ([-2.0,  2.0,  0.0, 2.0, 2.0],      -16.0,    0.1,      7.0,  0.0),
+    ([ 0.0,  1.0,  0.0, 0.0, 1.0],      -15.0,    0.0,      9.0,  0.0),
+    ([-2.0,  0.0,  1.0, 0.0, 1.0],      -13.0,    0.0,      7.0,  0.0),
+    ([ 0.0, -1.0,  0.0, 0.0, 1.0],      -12.0,    0.0,      6.0,  0.0),
+    ([ 0.0,  0.0,  2.0, -2.0, 0.0],      11.0,    0.0,      0.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 2.0, 1.0],      -10.0,    0.0,      5.0,  0.0),
+];
+
+/// Nutation angles and obliquity of the ecliptic, in radians.
+pub struct Nutation {
+    /// Nutation in longitude, Δψ.
+    pub longitude: f64,
+    /// Nutation in obliquity, Δε.
+    pub obliquity: f64,
+    /// Mean obliquity of the ecliptic, ε̄.
+    pub mean_obliquity: f64,
+}
+
+impl Nutation {
+    pub fn true_obliquity(&self) -> f64 {
+        self.mean_obliquity + self.obliquity
+    }
+
+    /// Equation of the equinoxes, the difference between apparent and mean sidereal time.
+    ///
+    /// The 1982 form without the Ω terms added in 1997, as used to define TEME.
+    pub fn equation_of_equinoxes(&self) -> f64 {
+        self.longitude * self.mean_obliquity.cos()
+    }
+}
+
+/// IAU-1980 nutation at `t` Julian centuries since J2000.0.
+pub fn nutation(t: f64) -> Nutation {
+    let polynomial = |c0: f64, c1: f64, c2: f64, c3: f64| {
+        c3.mul_add(t, c2).mul_add(t, c1).mul_add(t, c0).to_radians()
+    };
+
+    // fundamental arguments (Meeus, Ch. 22)
+    let arguments = [
+        polynomial(297.850_36, 445_267.111_480, -0.001_914_2, 1.0 / 189_474.0),
+        polynomial(357.527_72, 35_999.050_340, -0.000_160_3, -1.0 / 300_000.0),
+        polynomial(134.962_98, 477_198.867_398, 0.008_697_2, 1.0 / 56_250.0),
+        polynomial(93.271_91, 483_202.017_538, -0.003_682_5, 1.0 / 327_270.0),
+        polynomial(125.044_52, -1_934.136_261, 0.002_070_8, 1.0 / 450_000.0),
+    ];
+
+    let (longitude, obliquity) =
+        NUTATION_TERMS
+            .iter()
+            .fold((0.0, 0.0), |(dpsi, deps), (multipliers, s0, s1, c0, c1)| {
+                let argument: f64 = multipliers.iter().zip(arguments).map(|(k, a)| k * a).sum();
+                (
+                    s1.mul_add(t, *s0).mul_add(argument.sin(), dpsi),
+                    c1.mul_add(t, *c0).mul_add(argument.cos(), deps),
+                )
+            });
+
+    let mean_obliquity_arcsec = 0.001_813_f64
+        .mul_add(t, -0.000_59)
+        .mul_add(t, -46.815_0)
+        .mul_add(t, 84_381.448);
+
+    Nutation {
+        longitude: longitude * 1e-4 * ARCSEC_TO_RAD,
+        obliquity: obliquity * 1e-4 * ARCSEC_TO_RAD,
+        mean_obliquity: mean_obliquity_arcsec * ARCSEC_TO_RAD,
+    }
+}
+
+/// IAU-1976 precession from the J2000 mean equator and equinox to the mean of date.
+pub fn precession_matrix(t: f64) -> Matrix3 {
+    let zeta = 0.017_998_f64.mul_add(t, 0.301_88).mul_add(t, 2_306.218_1) * t * ARCSEC_TO_RAD;
+    let z = 0.018_203_f64.mul_add(t, 1.094_68).mul_add(t, 2_306.218_1) * t * ARCSEC_TO_RAD;
+    let theta = (-0.041_833_f64)
+        .mul_add(t, -0.426_65)
+        .mul_add(t, 2_004.310_9)
+        * t
+        * ARCSEC_TO_RAD;
+
+    multiply(&rot_z(-z), &multiply(&rot_y(theta), &rot_z(-zeta)))
+}
+
+/// Nutation from the mean to the true equator and equinox of date.
+pub fn nutation_matrix(nutation: &Nutation) -> Matrix3 {
+    multiply(
+        &rot_x(-nutation.true_obliquity()),
+        &multiply(&rot_z(-nutation.longitude), &rot_x(nutation.mean_obliquity)),
+    )
+}
+
+/// Rotation from TEME to the J2000 mean equator and equinox at `datetime`.
+///
+/// TEME shares the true equator of date but measures right ascension from the mean
+/// equinox, so it is first brought to the true equinox with the equation of the
+/// equinoxes and then undone through nutation and precession.
+pub fn teme_to_j2000(datetime: DateTime<Utc>) -> Matrix3 {
+    let t = julian_centuries_tt(datetime);
+    let nutation = nutation(t);
+
+    let teme_to_tod = rot_z(-nutation.equation_of_equinoxes());
+    let tod_to_mod = transpose(&nutation_matrix(&nutation));
+    let mod_to_j2000 = transpose(&precession_matrix(t));
+
+    multiply(&mod_to_j2000, &multiply(&tod_to_mod, &teme_to_tod))
+}
@@ -1,37 +1,179 @@
 use chrono::{DateTime, Utc};
+use std::f64::consts::PI;
 
-use crate::astro::coords::eci::Eci;
-use crate::astro::models::Tle;
+use crate::astro::analytic::{J2SecularPropagator, TwoBodyPropagator};
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::cowell::CowellPropagator;
+use crate::astro::element_set::ElementSet;
+use crate::astro::interpolation::Ephemeris;
+use crate::astro::models::{
+    GravityModel, PropagationMethod, PropagationModel, PropagatorKind, Resonance, Tle,
+};
 use crate::domain::errors::PropagationError;
 
+/// Model turning an element set into TEME states.
+pub trait OrbitPropagator: Send + Sync {
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError>;
+
+    fn method(&self) -> PropagationMethod;
+}
+
+/// Element set with the model propagating it.
 pub struct Propagator {
-    pub elements: sgp4::Elements,
-    pub constants: sgp4::Constants,
+    /// Mean elements of the element set, whichever model propagates them; `None` for
+    /// interpolated ephemerides.
+    pub elements: Option<sgp4::Elements>,
+    model: Box<dyn OrbitPropagator>,
 }
 
 impl Propagator {
-    pub fn from_tle(tle: &Tle) -> Result<Self, PropagationError> {
-        let elements = sgp4::Elements::from_tle(
-            Some(tle.satellite_name.clone()),
-            tle.line1.as_bytes(),
-            tle.line2.as_bytes(),
-        )?;
+    pub fn from_tle(tle: &Tle, kind: PropagatorKind) -> Result<Self, PropagationError> {
+        let element_set =
+            ElementSet::from_tle(Some(tle.satellite_name.clone()), &tle.line1, &tle.line2)
+                .map_err(PropagationError::TleParse)?;
+
+        Self::from_element_set(&element_set, kind)
+    }
 
-        let constants = sgp4::Constants::from_elements(&elements)?;
+    /// Build the model from the element set; the models other than SGP4 start from the
+    /// SGP4 state or Brouwer mean elements at its epoch.
+    pub fn from_element_set(
+        element_set: &ElementSet,
+        kind: PropagatorKind,
+    ) -> Result<Self, PropagationError> {
+        let elements = sgp4::Elements::from(element_set);
+        let sgp4 = Sgp4Propagator::new(&elements)?;
+
+        let model: Box<dyn OrbitPropagator> = match kind {
+            PropagatorKind::Sgp4 => Box::new(sgp4),
+            PropagatorKind::TwoBody => Box::new(TwoBodyPropagator::new(&sgp4.epoch_state()?)),
+            PropagatorKind::J2Secular => Box::new(J2SecularPropagator::new(&elements)?),
+            PropagatorKind::Cowell => Box::new(CowellPropagator::new(
+                &sgp4.epoch_state()?,
+                elements.drag_term,
+            )),
+        };
 
         Ok(Self {
-            elements,
-            constants,
+            elements: Some(elements),
+            model,
         })
     }
 
+    pub fn from_ephemeris(ephemeris: Ephemeris) -> Self {
+        Self {
+            elements: None,
+            model: Box::new(ephemeris),
+        }
+    }
+
+    pub fn method(&self) -> PropagationMethod {
+        self.model.method()
+    }
+
     pub fn eci_at(&self, datetime: DateTime<Utc>) -> Result<Eci, PropagationError> {
+        self.state_at(datetime).map(|(position, _)| position)
+    }
+
+    pub fn state_at(
+        &self,
+        datetime: DateTime<Utc>,
+    ) -> Result<(Eci, EciVelocity), PropagationError> {
+        self.model.state_at(datetime)
+    }
+}
+
+/// TEME position and velocity, in km and km/s, at a given time.
+#[derive(Debug, Clone, Copy)]
+pub struct StateVector {
+    pub epoch: DateTime<Utc>,
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+pub struct Sgp4Propagator {
+    elements: sgp4::Elements,
+    constants: sgp4::Constants,
+    method: PropagationMethod,
+}
+
+impl Sgp4Propagator {
+    pub fn new(elements: &sgp4::Elements) -> Result<Self, PropagationError> {
+        Ok(Self {
+            elements: elements.clone(),
+            constants: sgp4::Constants::from_elements(elements)?,
+            method: sgp4_method(&brouwer_orbit(elements)?),
+        })
+    }
+
+    /// State at the element set epoch, where the other models start from.
+    pub fn epoch_state(&self) -> Result<StateVector, PropagationError> {
+        let prediction = self.constants.propagate(sgp4::MinutesSinceEpoch(0.0))?;
+
+        Ok(StateVector {
+            epoch: self.elements.datetime.and_utc(),
+            position: prediction.position,
+            velocity: prediction.velocity,
+        })
+    }
+}
+
+impl OrbitPropagator for Sgp4Propagator {
+    fn state_at(&self, datetime: DateTime<Utc>) -> Result<(Eci, EciVelocity), PropagationError> {
         let minutes_since_epoch = self
             .elements
             .datetime_to_minutes_since_epoch(&datetime.naive_utc())?;
 
         let prediction = self.constants.propagate(minutes_since_epoch)?;
 
-        Ok(Eci::from(prediction.position))
+        Ok((
+            Eci::from(prediction.position),
+            EciVelocity::from(prediction.velocity),
+        ))
+    }
+
+    fn method(&self) -> PropagationMethod {
+        self.method
+    }
+}
+
+/// Brouwer mean elements SGP4 recovers from the Kozai ones of the element set, with the
+/// WGS84 constants of `sgp4::Constants::from_elements`.
+pub fn brouwer_orbit(elements: &sgp4::Elements) -> Result<sgp4::Orbit, sgp4::ElementsError> {
+    sgp4::Orbit::from_kozai_elements(
+        &sgp4::WGS84,
+        elements.inclination.to_radians(),
+        elements.right_ascension.to_radians(),
+        elements.eccentricity,
+        elements.argument_of_perigee.to_radians(),
+        elements.mean_anomaly.to_radians(),
+        // revolutions per day to radians per minute
+        elements.mean_motion * (PI / 720.0),
+    )
+    .map_err(sgp4::ElementsError::from)
+}
+
+/// Branch `sgp4::Constants` takes for an orbit, which it does not expose: deep space
+/// from a period of 225 minutes, with resonance terms for Brouwer mean motions between
+/// 0.0034906585 and 0.0052359877 rad/min (one day) or between 8.26e-3 and 9.24e-3
+/// rad/min with an eccentricity of at least 0.5 (half day).
+fn sgp4_method(orbit: &sgp4::Orbit) -> PropagationMethod {
+    let n = orbit.mean_motion;
+
+    let (model, resonance) = if n > 2.0 * PI / 225.0 {
+        (PropagationModel::Sgp4, None)
+    } else if n > 0.003_490_658_5 && n < 0.005_235_987_7 {
+        (PropagationModel::Sdp4, Some(Resonance::OneDay))
+    } else if (8.26e-3..=9.24e-3).contains(&n) && orbit.eccentricity >= 0.5 {
+        (PropagationModel::Sdp4, Some(Resonance::HalfDay))
+    } else {
+        (PropagationModel::Sdp4, Some(Resonance::None))
+    };
+
+    PropagationMethod {
+        model,
+        resonance,
+        gravity_model: Some(GravityModel::Wgs84),
+        afspc_compatibility_mode: false,
     }
 }
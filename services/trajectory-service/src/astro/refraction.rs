@@ -0,0 +1,90 @@
+use uom::si::angle::{degree, minute};
+use uom::si::f64::{Angle, Pressure, ThermodynamicTemperature};
+use uom::si::pressure::hectopascal;
+use uom::si::thermodynamic_temperature::kelvin;
+
+/// Lowest geometric elevation, in degrees, at which the refraction formula is applied.
+///
+/// The empirical formula diverges further below the horizon, where the bending is
+/// no longer meaningful for pointing anyway.
+const MIN_ELEVATION_DEG: f64 = -1.0;
+
+/// Depth, in degrees below [`MIN_ELEVATION_DEG`], over which the refraction is tapered
+/// linearly to zero, so the apparent elevation stays continuous for the horizon search.
+const TAPER_DEG: f64 = 1.0;
+
+/// Pressure the refraction formula is calibrated for, in hectopascals.
+const REFERENCE_PRESSURE_HPA: f64 = 1010.0;
+
+/// Temperature the refraction formula is calibrated for, in kelvins.
+const REFERENCE_TEMPERATURE_K: f64 = 283.15;
+
+/// Local conditions at the observer that determine atmospheric refraction.
+pub struct Atmosphere {
+    pub pressure: Pressure,
+    pub temperature: ThermodynamicTemperature,
+}
+
+impl Default for Atmosphere {
+    /// Standard conditions the refraction formula is calibrated for: 1010 hPa and 10 °C.
+    fn default() -> Self {
+        Self {
+            pressure: Pressure::new::<hectopascal>(REFERENCE_PRESSURE_HPA),
+            temperature: ThermodynamicTemperature::new::<kelvin>(REFERENCE_TEMPERATURE_K),
+        }
+    }
+}
+
+impl Atmosphere {
+    /// Refraction for a body at geometric (airless) `elevation`.
+    ///
+    /// Sæmundsson (1986) formula, as given in Meeus, *Astronomical Algorithms*, Ch. 16,
+    /// scaled for pressure and temperature. Accurate to about 0.1′ above 15° and a few
+    /// arcminutes near the horizon; tapered from its value at -1° to zero at -2°.
+    pub fn refraction(&self, elevation: Angle) -> Angle {
+        let h = elevation.get::<degree>();
+        if !(MIN_ELEVATION_DEG - TAPER_DEG..=90.0).contains(&h) {
+            return Angle::new::<degree>(0.0);
+        }
+        let taper = ((h - MIN_ELEVATION_DEG) / TAPER_DEG + 1.0).min(1.0);
+        let h = h.max(MIN_ELEVATION_DEG);
+
+        // the constant term makes the refraction vanish at the zenith
+        let standard_arcmin = 1.02 / (h + 10.3 / (h + 5.11)).to_radians().tan() + 0.001_927_9;
+        let scale = (self.pressure.get::<hectopascal>() / REFERENCE_PRESSURE_HPA)
+            * (REFERENCE_TEMPERATURE_K / self.temperature.get::<kelvin>());
+
+        Angle::new::<minute>(standard_arcmin * scale * taper)
+    }
+
+    /// Elevation at which the body actually appears, raised by refraction.
+    pub fn apparent_elevation(&self, elevation: Angle) -> Angle {
+        elevation + self.refraction(elevation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apparent_deg(elevation_deg: f64) -> f64 {
+        Atmosphere::default()
+            .apparent_elevation(Angle::new::<degree>(elevation_deg))
+            .get::<degree>()
+    }
+
+    #[test]
+    fn apparent_elevation_is_continuous_and_increasing_below_the_horizon() {
+        let step = 1e-3;
+        let mut previous = apparent_deg(-3.0);
+        for i in 1..=3000 {
+            let elevation = f64::from(i).mul_add(step, -3.0);
+            let apparent = apparent_deg(elevation);
+
+            assert!(apparent > previous, "not increasing at {elevation}°");
+            assert!(apparent - previous < 0.01, "jumps at {elevation}°");
+            previous = apparent;
+        }
+        assert!((apparent_deg(-2.5) + 2.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,54 @@
+//! Elementary 3×3 rotations used by the frame transformations.
+//!
+//! Rotations are passive (they rotate the axes, not the vector), following the
+//! `R₁`, `R₂`, `R₃` convention of the IERS Conventions and Vallado.
+
+pub type Matrix3 = [[f64; 3]; 3];
+
+pub const IDENTITY: Matrix3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Rotation of the axes by `angle` radians about the x axis.
+pub fn rot_x(angle: f64) -> Matrix3 {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+/// Rotation of the axes by `angle` radians about the y axis.
+pub fn rot_y(angle: f64) -> Matrix3 {
+    let (s, c) = angle.sin_cos();
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+/// Rotation of the axes by `angle` radians about the z axis.
+pub fn rot_z(angle: f64) -> Matrix3 {
+    let (s, c) = angle.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+pub fn multiply(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut product = [[0.0; 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    product
+}
+
+pub fn transpose(m: &Matrix3) -> Matrix3 {
+    let mut transposed = [[0.0; 3]; 3];
+    for (i, row) in m.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            transposed[j][i] = value;
+        }
+    }
+    transposed
+}
+
+pub fn apply(m: &Matrix3, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
@@ -0,0 +1,239 @@
+//! One-dimensional root finding and extremum search over propagated quantities.
+//!
+//! The objective functions wrap SGP4 propagation, so they are fallible and every
+//! evaluation is comparatively expensive; both methods aim to converge in as few
+//! evaluations as possible.
+
+use crate::domain::errors::PropagationError;
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Inverse of the golden ratio, `(√5 − 1) / 2`.
+const INV_PHI: f64 = 0.618_033_988_749_894_8;
+
+/// Find a root of `f` inside `[a, b]` using Brent's method.
+///
+/// `f(a)` and `f(b)` must have opposite signs. Combines bisection, secant and
+/// inverse quadratic interpolation steps, so it keeps the guaranteed convergence of
+/// bisection while usually converging superlinearly.
+///
+/// Variable names follow Brent (1973), *Algorithms for Minimization without Derivatives*, Ch. 4.
+#[allow(clippy::many_single_char_names)]
+pub fn find_root<F>(mut f: F, a: f64, b: f64, tolerance: f64) -> Result<f64, PropagationError>
+where
+    F: FnMut(f64) -> Result<f64, PropagationError>,
+{
+    let (mut a, mut b) = (a, b);
+    let mut fa = f(a)?;
+    let mut fb = f(b)?;
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..MAX_ITERATIONS {
+        if fb * fc > 0.0 {
+            // root is between a and b, reset c
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+
+        if fc.abs() < fb.abs() {
+            // keep b as the best estimate so far
+            (a, b, c) = (b, c, b);
+            (fa, fb, fc) = (fb, fc, fb);
+        }
+
+        let tol = (2.0 * f64::EPSILON).mul_add(b.abs(), 0.5 * tolerance);
+        let m = 0.5 * (c - b);
+
+        if m.abs() <= tol {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            let s = fb / fa;
+
+            let (mut p, mut q) = if (a - c).abs() <= f64::EPSILON {
+                // secant step
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                // inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                // interpolation accepted
+                e = d;
+                d = p / q;
+            } else {
+                // fall back to bisection
+                d = m;
+                e = d;
+            }
+        } else {
+            d = m;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b)?;
+    }
+
+    Ok(b)
+}
+
+/// Find the maximum of a unimodal function `f` inside `[a, b]` using golden-section search.
+///
+/// Returns the argument of the maximum together with the function value there.
+pub fn find_maximum<F>(
+    mut f: F,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<(f64, f64), PropagationError>
+where
+    F: FnMut(f64) -> Result<f64, PropagationError>,
+{
+    let (mut lo, mut hi) = (a, b);
+
+    let mut x1 = INV_PHI.mul_add(-(hi - lo), hi);
+    let mut x2 = INV_PHI.mul_add(hi - lo, lo);
+    let mut f1 = f(x1)?;
+    let mut f2 = f(x2)?;
+
+    for _ in 0..MAX_ITERATIONS {
+        if hi - lo <= tolerance {
+            break;
+        }
+
+        if f1 < f2 {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = INV_PHI.mul_add(hi - lo, lo);
+            f2 = f(x2)?;
+        } else {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = INV_PHI.mul_add(-(hi - lo), hi);
+            f1 = f(x1)?;
+        }
+    }
+
+    Ok(if f1 < f2 { (x2, f2) } else { (x1, f1) })
+}
+
+/// Find the sub-intervals of a sampled range where `f` is negative.
+///
+/// `samples` holds `(x, f(x))` pairs in increasing `x`. Sign changes between
+/// neighbouring samples are refined with [`find_root`]; intervals still open at
+/// either end are clipped to the first or last sample.
+pub fn negative_intervals<F>(
+    samples: &[(f64, f64)],
+    f: F,
+    tolerance: f64,
+) -> Result<Vec<(f64, f64)>, PropagationError>
+where
+    F: Fn(f64) -> Result<f64, PropagationError>,
+{
+    let mut intervals = Vec::new();
+    let mut entry = samples.first().and_then(|&(x, fx)| (fx < 0.0).then_some(x));
+
+    for pair in samples.windows(2) {
+        let ((x0, f0), (x1, f1)) = (pair[0], pair[1]);
+
+        if f0 >= 0.0 && f1 < 0.0 {
+            entry = Some(find_root(&f, x0, x1, tolerance)?);
+        } else if f0 < 0.0
+            && f1 >= 0.0
+            && let Some(start) = entry.take()
+        {
+            intervals.push((start, find_root(&f, x0, x1, tolerance)?));
+        }
+    }
+
+    if let (Some(start), Some(&(last, _))) = (entry, samples.last()) {
+        intervals.push((start, last));
+    }
+
+    Ok(intervals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_root_converges_on_analytic_functions() {
+        let root = find_root(|x| Ok(x.cos()), 0.0, 3.0, 1e-12).unwrap();
+        assert!((root - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        // Wallis' cubic, the classic test of the method
+        let root = find_root(|x| Ok(x.powi(3) - 2.0 * x - 5.0), 2.0, 3.0, 1e-12).unwrap();
+        assert!((root - 2.094_551_481_542_326_5).abs() < 1e-12);
+
+        // either end may hold the negative value
+        let root = find_root(|x| Ok(1.0 - x * x), 0.0, 5.0, 1e-12).unwrap();
+        assert!((root - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn find_root_propagates_errors() {
+        let failing = |_| Err(PropagationError::EphemerisNotSupported);
+        assert!(find_root(failing, 0.0, 1.0, 1e-9).is_err());
+    }
+
+    #[test]
+    fn find_maximum_converges_on_unimodal_functions() {
+        let (x, value) = find_maximum(|x| Ok(-(x - 1.3).powi(2) + 2.0), 0.0, 4.0, 1e-9).unwrap();
+        assert!((x - 1.3).abs() < 1e-6);
+        assert!((value - 2.0).abs() < 1e-12);
+
+        let (x, value) = find_maximum(|x| Ok(x.sin()), 0.0, std::f64::consts::PI, 1e-9).unwrap();
+        assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((value - 1.0).abs() < 1e-12);
+
+        // maximum on the edge of the bracket
+        let (x, _) = find_maximum(Ok, 0.0, 1.0, 1e-9).unwrap();
+        assert!((x - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn negative_intervals_are_refined_and_clipped() {
+        let f = |x: f64| Ok(x.sin());
+        let samples: Vec<(f64, f64)> = (0..=11)
+            .map(|i| {
+                let x = f64::from(i) - 1.0;
+                (x, x.sin())
+            })
+            .collect();
+
+        let intervals = negative_intervals(&samples, f, 1e-12).unwrap();
+
+        // open at the first sample, then a full interval between π and 2π
+        assert_eq!(intervals.len(), 3);
+        assert!((intervals[0].0 + 1.0).abs() < f64::EPSILON);
+        assert!(intervals[0].1.abs() < 1e-12);
+        assert!((intervals[1].0 - std::f64::consts::PI).abs() < 1e-12);
+        assert!((intervals[1].1 - 2.0 * std::f64::consts::PI).abs() < 1e-12);
+        // still negative at the last sample
+        assert!((intervals[2].0 - 3.0 * std::f64::consts::PI).abs() < 1e-12);
+        assert!((intervals[2].1 - 10.0).abs() < f64::EPSILON);
+    }
+}
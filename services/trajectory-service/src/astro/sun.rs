@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use uom::si::f64::Length;
+use uom::si::length::kilometer;
+
+use crate::astro::consts::AU;
+use crate::astro::coords::eci::Eci;
+use crate::astro::time::julian_centuries_tt;
+
+/// Geocentric position of the Sun in the mean equator and equinox of date.
+///
+/// Low-precision solar coordinates from the Astronomical Almanac (also Vallado,
+/// *Fundamentals of Astrodynamics and Applications*, Algorithm 29), accurate to about
+/// 0.01° over 1950–2050. The frame differs from TEME by less than the model error,
+/// so the result can be used directly alongside SGP4 output.
+pub fn sun_eci(datetime: DateTime<Utc>) -> Eci {
+    let t = julian_centuries_tt(datetime);
+
+    let mean_longitude = 280.460_f64.to_radians() + 36_000.771_f64.to_radians() * t;
+    let mean_anomaly = 357.529_109_2_f64.to_radians() + 35_999.050_34_f64.to_radians() * t;
+
+    let ecliptic_longitude = mean_longitude
+        + 1.914_666_471_f64.to_radians() * mean_anomaly.sin()
+        + 0.019_994_643_f64.to_radians() * (2.0 * mean_anomaly).sin();
+
+    let obliquity = 23.439_291_f64.to_radians() - 0.013_004_2_f64.to_radians() * t;
+
+    let distance_au = 1.000_140_612
+        - 0.016_708_617 * mean_anomaly.cos()
+        - 0.000_139_589 * (2.0 * mean_anomaly).cos();
+    let distance_km = distance_au * AU;
+
+    Eci {
+        x: Length::new::<kilometer>(distance_km * ecliptic_longitude.cos()),
+        y: Length::new::<kilometer>(distance_km * obliquity.cos() * ecliptic_longitude.sin()),
+        z: Length::new::<kilometer>(distance_km * obliquity.sin() * ecliptic_longitude.sin()),
+    }
+}
@@ -1,17 +1,191 @@
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono::{DateTime, Datelike, TimeDelta, Timelike, Utc};
 use uom::si::angle::{degree, radian};
 use uom::si::f64::{Angle, Time};
 use uom::si::time::second;
 
 use super::consts::{
     DAYS_IN_MONTH, DAYS_IN_YEAR, DAYS_PER_CENTURY, GMST_BASE, GMST_COEFF1, GMST_COEFF2,
-    GMST_COEFF3, JULIAN_DAY_BASE, JULIAN_DAY_OFFSET, JULIAN_YEAR_OFFSET, SECONDS_TO_DEGREES,
-    TWO_PI,
+    GMST_COEFF3, JULIAN_DAY_BASE, JULIAN_DAY_OFFSET, JULIAN_YEAR_OFFSET, MJD_OFFSET,
+    SECONDS_PER_DAY, SECONDS_TO_DEGREES, TAI_MINUS_GPS, TT_MINUS_TAI, TWO_PI,
 };
+use crate::astro::eop::EopTable;
+
+/// TAI − UTC in whole seconds, from the UTC instant it takes effect (as Unix time).
+///
+/// From IERS Bulletin C; the leap second of 2016-12-31 is the latest announced.
+/// Must be extended whenever a new one is announced.
+const LEAP_SECONDS: [(i64, i32); 28] = [
+    (63_072_000, 10),    // 1972-01-01
+    (78_796_800, 11),    // 1972-07-01
+    (94_694_400, 12),    // 1973-01-01
+    (126_230_400, 13),   // 1974-01-01
+    (157_766_400, 14),   // 1975-01-01
+    (189_302_400, 15),   // 1976-01-01
+    (220_924_800, 16),   // 1977-01-01
+    (252_460_800, 17),   // 1978-01-01
+    (283_996_800, 18),   // 1979-01-01
+    (315_532_800, 19),   // 1980-01-01
+    (362_793_600, 20),   // 1981-07-01
+    (394_329_600, 21),   // 1982-07-01
+    (425_865_600, 22),   // 1983-07-01
+    (489_024_000, 23),   // 1985-07-01
+    (567_993_600, 24),   // 1988-01-01
+    (631_152_000, 25),   // 1990-01-01
+    (662_688_000, 26),   // 1991-01-01
+    (709_948_800, 27),   // 1992-07-01
+    (741_484_800, 28),   // 1993-07-01
+    (773_020_800, 29),   // 1994-07-01
+    (820_454_400, 30),   // 1996-01-01
+    (867_715_200, 31),   // 1997-07-01
+    (915_148_800, 32),   // 1999-01-01
+    (1_136_073_600, 33), // 2006-01-01
+    (1_230_768_000, 34), // 2009-01-01
+    (1_341_100_800, 35), // 2012-07-01
+    (1_435_708_800, 36), // 2015-07-01
+    (1_483_228_800, 37), // 2017-01-01
+];
+
+/// Time scales timestamps can be expressed in.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, the scale of `DateTime<Utc>` values.
+    #[default]
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, the argument of the precession and nutation theories.
+    Tt,
+    /// Universal Time tied to Earth rotation, the argument of sidereal time.
+    Ut1,
+    /// GPS system time, a fixed 19 s behind TAI.
+    Gps,
+}
+
+impl TimeScale {
+    /// Seconds to add to the UTC instant `utc` to read the same instant in this scale.
+    pub fn offset_from_utc(self, utc: DateTime<Utc>, ut1_minus_utc: f64) -> f64 {
+        match self {
+            Self::Utc => 0.0,
+            Self::Tai => tai_minus_utc(utc),
+            Self::Tt => tai_minus_utc(utc) + TT_MINUS_TAI,
+            Self::Ut1 => ut1_minus_utc,
+            Self::Gps => tai_minus_utc(utc) - TAI_MINUS_GPS,
+        }
+    }
+}
+
+/// Converts between UTC instants and the reading of a clock in another time scale.
+///
+/// Readings are carried in `DateTime<Utc>` values for lack of a scale-aware type:
+/// only their calendar fields are meaningful. UT1 readings use the Earth orientation
+/// parameters, and coincide with UTC outside their coverage.
+#[derive(Clone, Copy)]
+pub struct Clock<'a> {
+    pub scale: TimeScale,
+    eop: &'a EopTable,
+}
+
+impl<'a> Clock<'a> {
+    pub const fn new(scale: TimeScale, eop: &'a EopTable) -> Self {
+        Self { scale, eop }
+    }
+
+    /// Reading of this clock at the UTC instant `utc`.
+    pub fn reading(&self, utc: DateTime<Utc>) -> DateTime<Utc> {
+        add_seconds(utc, self.offset_at(utc))
+    }
+
+    /// UTC instant at which this clock shows `reading`.
+    ///
+    /// The offset is evaluated at the reading shifted by a first guess of it, which
+    /// picks the right side of leap seconds; readings inside an inserted leap second
+    /// have no UTC instant and map to the following second.
+    pub fn utc(&self, reading: DateTime<Utc>) -> DateTime<Utc> {
+        let guess = add_seconds(reading, -self.offset_at(reading));
+        add_seconds(reading, -self.offset_at(guess))
+    }
+
+    fn offset_at(&self, utc: DateTime<Utc>) -> f64 {
+        let ut1_minus_utc = match self.scale {
+            TimeScale::Ut1 => self.eop.orientation_at(utc).ut1_minus_utc,
+            _ => 0.0,
+        };
+        self.scale.offset_from_utc(utc, ut1_minus_utc)
+    }
+}
+
+/// TAI − UTC at the UTC instant `utc`, in seconds.
+///
+/// Instants before 1972 use the initial 10 s, as UTC was not yet stepped in whole
+/// seconds.
+pub fn tai_minus_utc(utc: DateTime<Utc>) -> f64 {
+    let unix = utc.timestamp();
+    let index = LEAP_SECONDS.partition_point(|&(since, _)| since <= unix);
+    let (_, seconds) = LEAP_SECONDS[index.saturating_sub(1)];
+    f64::from(seconds)
+}
+
+/// Julian date in two parts, to keep sub-microsecond resolution.
+///
+/// A single `f64` near JD 2.46e6 only resolves about 40 µs, so the date is held as
+/// the Julian date of the preceding midnight plus the fraction of the day elapsed.
+#[derive(Clone, Copy)]
+pub struct JulianDate {
+    /// Julian date of 0h, always ending in .5.
+    pub day: f64,
+    /// Fraction of a day since `day`, normally within [0, 1).
+    pub fraction: f64,
+}
+
+impl JulianDate {
+    /// Julian date of the calendar reading of `datetime`.
+    pub fn from_datetime(datetime: &DateTime<Utc>) -> Self {
+        Self {
+            day: julian_day_number(datetime),
+            fraction: day_fraction(datetime),
+        }
+    }
+
+    /// Julian date of the UTC instant `utc` in the given time scale.
+    pub fn in_scale(utc: DateTime<Utc>, scale: TimeScale, ut1_minus_utc: f64) -> Self {
+        Self::from_datetime(&utc).add_seconds(scale.offset_from_utc(utc, ut1_minus_utc))
+    }
+
+    pub fn add_seconds(self, seconds: f64) -> Self {
+        Self {
+            day: self.day,
+            fraction: self.fraction + seconds / SECONDS_PER_DAY,
+        }
+    }
+
+    /// Julian centuries elapsed since J2000.0.
+    pub fn centuries_since_j2000(self) -> f64 {
+        ((self.day - JULIAN_DAY_BASE) + self.fraction) / DAYS_PER_CENTURY
+    }
+
+    /// Modified Julian date (days since 1858-11-17 0h).
+    pub fn modified(self) -> f64 {
+        (self.day - MJD_OFFSET) + self.fraction
+    }
+}
+
+/// Julian centuries of Terrestrial Time since J2000.0 at the UTC instant `utc`.
+///
+/// The time argument of the precession, nutation, solar and lunar theories.
+pub fn julian_centuries_tt(utc: DateTime<Utc>) -> f64 {
+    JulianDate::in_scale(utc, TimeScale::Tt, 0.0).centuries_since_j2000()
+}
 
 /// Convert UTC datetime to Greenwich Sidereal Time (GST) in radians.
 ///
-/// GST is the angle between the Greenwich meridian and the vernal equinox,
+/// Treats UTC as UT1; see [`gmst`] for the exact form.
+pub fn utc_to_gst(datetime: DateTime<Utc>) -> Angle {
+    gmst(JulianDate::from_datetime(&datetime))
+}
+
+/// Greenwich Mean Sidereal Time at the UT1 Julian date `ut1`, in [0, 2π).
+///
+/// GMST is the angle between the Greenwich meridian and the vernal equinox,
 /// and is used as an intermediate step for converting between ECI (Earth-Centered Inertial)
 /// and ECEF (Earth-Centered Earth-Fixed) coordinate frames.
 ///
@@ -19,12 +193,10 @@ use super::consts::{
 /// - Astronomical Almanac, 1984, p. B6
 /// - Jean Meeus, *Astronomical Algorithms*, Ch. 12
 /// - [SOFA/IAU Standards](https://www.iausofa.org/)
-pub fn utc_to_gst(datetime: DateTime<Utc>) -> Angle {
-    let jd = datetime_to_julian(&datetime);
-
+pub fn gmst(ut1: JulianDate) -> Angle {
     // julian centuries since J2000.0
     // J2000.0 corresponds to JD = 2451545.0 (2000-01-01 12:00 TT)
-    let t = (jd - JULIAN_DAY_BASE) / DAYS_PER_CENTURY;
+    let t = ut1.centuries_since_j2000();
 
     // compute Greenwich Mean Sidereal Time (GMST) in seconds
     let gmst_sec = gmst_seconds(t);
@@ -36,21 +208,28 @@ pub fn utc_to_gst(datetime: DateTime<Utc>) -> Angle {
     normalize_angle(angle)
 }
 
-/// Convert a UTC datetime into Julian Date (JD).
+/// Shift a datetime by a fractional number of seconds, with nanosecond resolution.
+#[allow(clippy::cast_possible_truncation)]
+pub fn add_seconds(datetime: DateTime<Utc>, seconds: f64) -> DateTime<Utc> {
+    datetime + TimeDelta::nanoseconds((seconds * 1e9).round() as i64)
+}
+
+/// Number of seconds elapsed from `from` to `to`, negative if `to` is earlier.
+pub fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).as_seconds_f64()
+}
+
+/// Julian Date (JD) of the midnight starting the calendar day of `datetime`.
 ///
 /// Julian Date is a continuous count of days since 4713 BC, used in astronomy.
 /// Formula follows Meeus (1998), Ch. 7.
 ///
 /// See: [NASA Julian Date explanation](https://ssd.jpl.nasa.gov/tools/jdc/#/)
-fn datetime_to_julian(datetime: &DateTime<Utc>) -> f64 {
+fn julian_day_number(datetime: &DateTime<Utc>) -> f64 {
     let year = datetime.year();
     let month = datetime.month();
     let day = datetime.day();
 
-    let hour = f64::from(datetime.hour());
-    let min = f64::from(datetime.minute());
-    let sec = f64::from(datetime.second());
-
     // adjust months so jan/feb are treated as months 13/14 of previous year
     let (yy, mm) = if month <= 2 {
         (year - 1, month + 12)
@@ -63,16 +242,18 @@ fn datetime_to_julian(datetime: &DateTime<Utc>) -> f64 {
     let b = 2.0 - a + (a / 4.0).floor();
 
     // integer Julian day number (midnight-based)
-    let jd_day = (DAYS_IN_YEAR * (f64::from(yy) + JULIAN_YEAR_OFFSET)).floor()
+    (DAYS_IN_YEAR * (f64::from(yy) + JULIAN_YEAR_OFFSET)).floor()
         + (DAYS_IN_MONTH * (f64::from(mm) + 1.0)).floor()
         + f64::from(day)
         + b
-        - JULIAN_DAY_OFFSET;
-
-    // fractional day from time-of-day
-    let day_fraction = (hour + min / 60.0 + sec / 3600.0) / 24.0;
+        - JULIAN_DAY_OFFSET
+}
 
-    jd_day + day_fraction
+/// Fraction of the day elapsed at `datetime`, down to the nanosecond.
+fn day_fraction(datetime: &DateTime<Utc>) -> f64 {
+    let seconds =
+        f64::from(datetime.num_seconds_from_midnight()) + f64::from(datetime.nanosecond()) * 1e-9;
+    seconds / SECONDS_PER_DAY
 }
 
 /// Compute GMST (Greenwich Mean Sidereal Time) in seconds.
@@ -98,12 +279,53 @@ fn gmst_seconds(t: f64) -> Time {
 
 /// Normalize an angle in radians to the range [0, 2π).
 fn normalize_angle(angle: Angle) -> Angle {
-    let wrapped = angle.get::<radian>() % TWO_PI;
-    if wrapped < 0.0 {
-        wrapped + TWO_PI
-    } else {
-        wrapped
-    };
+    Angle::new::<radian>(angle.get::<radian>().rem_euclid(TWO_PI))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().to_utc()
+    }
+
+    #[test]
+    fn steps_tai_minus_utc_at_the_2016_leap_second() {
+        assert!((tai_minus_utc(utc("2016-12-31T23:59:59Z")) - 36.0).abs() < f64::EPSILON);
+        assert!((tai_minus_utc(utc("2017-01-01T00:00:00Z")) - 37.0).abs() < f64::EPSILON);
+        assert!((tai_minus_utc(utc("1970-01-01T00:00:00Z")) - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn clock_readings_map_back_to_utc_across_a_leap_second() {
+        let eop = EopTable::empty();
+        let start = utc("2016-12-31T23:59:57Z");
+
+        for scale in [TimeScale::Tai, TimeScale::Tt, TimeScale::Gps] {
+            let clock = Clock::new(scale, &eop);
+
+            for half_seconds in 0..12 {
+                let instant = start + TimeDelta::milliseconds(500 * half_seconds);
+                assert_eq!(clock.utc(clock.reading(instant)), instant, "{instant}");
+            }
+        }
+    }
+
+    #[test]
+    fn julian_date_of_j2000() {
+        let date = JulianDate::from_datetime(&utc("2000-01-01T12:00:00Z"));
+
+        assert!((date.day - 2_451_544.5).abs() < f64::EPSILON);
+        assert!((date.fraction - 0.5).abs() < f64::EPSILON);
+        assert!(date.centuries_since_j2000().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn gmst_matches_vallado_example_3_5() {
+        // 1992-08-20 12:14 UT1
+        let angle = gmst(JulianDate::from_datetime(&utc("1992-08-20T12:14:00Z")));
 
-    Angle::new::<radian>(wrapped)
+        assert!((angle.get::<degree>() - 152.578_787_886).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use uom::si::angle::radian;
+use uom::si::f64::Angle;
+
+use crate::astro::celestial::CelestialBody;
+use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
+use crate::astro::models::{Pass, PassPoint, VisibleSegment};
+use crate::astro::propagator::Propagator;
+use crate::astro::refraction::Atmosphere;
+use crate::astro::search::{find_maximum, negative_intervals};
+use crate::astro::time::{add_seconds, seconds_between};
+use crate::domain::errors::PropagationError;
+
+/// Step of the visibility scan within a pass, in seconds.
+const SCAN_STEP_SECONDS: f64 = 10.0;
+
+/// Resolution of the refined segment boundaries, in seconds.
+const TIME_TOLERANCE_SECONDS: f64 = 1e-3;
+
+impl Propagator {
+    /// Split `pass` into the segments during which the satellite can be seen by eye.
+    ///
+    /// A satellite is optically visible while the Sun is below `max_sun_elevation` for
+    /// the observer and the satellite itself is outside Earth's umbra (a satellite in
+    /// penumbra still reflects sunlight). Both conditions are combined into a single
+    /// margin, negative while visible, whose sign changes are refined with Brent's method.
+    pub fn visible_segments(
+        &self,
+        pass: &Pass,
+        observer: &Geodetic,
+        max_sun_elevation: Angle,
+        atmosphere: Option<&Atmosphere>,
+        eop: &EopTable,
+    ) -> Result<Vec<VisibleSegment>, PropagationError> {
+        let start = pass.rise_time;
+        let duration = seconds_between(start, pass.set_time);
+        let threshold = max_sun_elevation.get::<radian>();
+
+        let hidden = |t: f64| -> Result<f64, PropagationError> {
+            let datetime = add_seconds(start, t);
+            let sun_elevation = CelestialBody::Sun
+                .horizon_at(datetime, observer)
+                .elevation
+                .get::<radian>();
+            let umbra_margin = self.shadow_at(datetime)?.umbra_margin();
+
+            // positive as soon as either the sky is too bright or the satellite is dark
+            Ok((sun_elevation - threshold).max(-umbra_margin))
+        };
+        let elevation = |t: f64| -> Result<f64, PropagationError> {
+            let horizon =
+                self.observed_horizon_at(add_seconds(start, t), observer, atmosphere, eop)?;
+            Ok(horizon.elevation.get::<radian>())
+        };
+
+        let samples = std::iter::successors(Some(0.0), |t| Some(t + SCAN_STEP_SECONDS))
+            .take_while(|&t| t < duration)
+            .chain(std::iter::once(duration))
+            .map(|t| hidden(t).map(|margin| (t, margin)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        negative_intervals(&samples, hidden, TIME_TOLERANCE_SECONDS)?
+            .into_iter()
+            .map(|(from, to)| {
+                let (max, _) = find_maximum(elevation, from, to, TIME_TOLERANCE_SECONDS)?;
+
+                Ok(VisibleSegment {
+                    start: self.pass_point(add_seconds(start, from), observer, atmosphere, eop)?,
+                    max: self.pass_point(add_seconds(start, max), observer, atmosphere, eop)?,
+                    end: self.pass_point(add_seconds(start, to), observer, atmosphere, eop)?,
+                })
+            })
+            .collect()
+    }
+
+    fn pass_point(
+        &self,
+        time: DateTime<Utc>,
+        observer: &Geodetic,
+        atmosphere: Option<&Atmosphere>,
+        eop: &EopTable,
+    ) -> Result<PassPoint, PropagationError> {
+        let horizon = self.observed_horizon_at(time, observer, atmosphere, eop)?;
+
+        Ok(PassPoint {
+            time,
+            azimuth: horizon.azimuth,
+            elevation: horizon.elevation,
+        })
+    }
+}
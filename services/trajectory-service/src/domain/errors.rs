@@ -1,4 +1,11 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use thiserror::Error;
+use tonic::Code;
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::astro::interpolation::InterpolationMethod;
+use crate::astro::models::{OrbitRegime, PropagatorKind};
 
 #[derive(Debug, Error)]
 pub enum StartupError {
@@ -12,6 +19,18 @@ pub enum StartupError {
     Grpc(#[from] GrpcServerError),
     #[error("Failed to initialize TLE gRPC client: {0}")]
     GrpcClientInit(#[from] tonic::transport::Error),
+    #[error("Failed to initialize metrics: {0}")]
+    Metrics(#[from] prometheus::Error),
+    #[error("Failed to read EOP file {0}: {1}")]
+    EopFile(String, std::io::Error),
+    #[error("No Earth orientation parameters in EOP file {0}, expected the IERS finals format")]
+    EopEmpty(String),
+    #[error("Failed to read ephemeris directory {0}: {1}")]
+    EphemerisDir(String, std::io::Error),
+    #[error("Failed to read ephemeris file {0}: {1}")]
+    EphemerisRead(String, std::io::Error),
+    #[error("Failed to parse ephemeris file {0}: {1}")]
+    EphemerisParse(String, EphemerisError),
 }
 
 impl From<std::io::Error> for StartupError {
@@ -40,42 +59,298 @@ pub enum GrpcServerError {
 pub enum PropagationError {
     #[error("Failed to fetch TLE: {0}")]
     TleFetch(#[from] tonic::Status),
+    #[error("Invalid inline element set: {0}")]
+    InvalidElementSet(#[from] ElementSetError),
     #[error("TLE parsing failed: {0}")]
-    TleParse(#[from] sgp4::TleError),
+    TleParse(ElementSetError),
     #[error("Failed to create SGP4 elements: {0}")]
     ElementsCreation(#[from] sgp4::ElementsError),
     #[error("Failed to convert datetime into minutes since epoch: {0}")]
     DatetimeToMinutesSinceEpochFailed(#[from] sgp4::DatetimeToMinutesSinceEpochError),
     #[error("SGP4 propagation failed: {0}")]
     PropagationFailed(#[from] sgp4::Error),
+    #[error("Propagation task failed: {0}")]
+    TaskFailed(#[from] tokio::task::JoinError),
+    #[error(
+        "TLE epoch {epoch} is {age_days:.1} days from the requested time, past the \
+         {max_days:.1} day limit for {regime}"
+    )]
+    TleTooOld {
+        epoch: DateTime<Utc>,
+        age_days: f64,
+        max_days: f64,
+        regime: OrbitRegime,
+    },
+    #[error("Satellite re-entered {minutes_since_epoch:.1} minutes after the epoch")]
+    Reentered { minutes_since_epoch: f64 },
+    #[error(
+        "Requested time is {days_since_epoch:.1} days from the epoch, past the \
+         {max_days:.1} day span of the numerical propagator"
+    )]
+    IntegrationSpanExceeded {
+        days_since_epoch: f64,
+        max_days: f64,
+    },
+    #[error("Numerical integration failed {minutes_since_epoch:.1} minutes after the epoch")]
+    IntegrationFailed { minutes_since_epoch: f64 },
+    #[error("Ephemeris {0} not found")]
+    EphemerisNotFound(String),
+    #[error("Ephemeris identifiers are only supported by GetPosition and GetLookAngles")]
+    EphemerisNotSupported,
+    #[error("Ephemerides are interpolated and cannot be propagated with {0:?}")]
+    PropagatorNotSupported(PropagatorKind),
+    #[error("Requested time {datetime} is outside the ephemeris coverage {start} to {stop}")]
+    OutsideEphemerisCoverage {
+        datetime: DateTime<Utc>,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+    },
 }
 
+/// Domain of the `google.rpc.ErrorInfo` attached to client-meaningful errors; the
+/// reasons within it are stable and meant to be matched on by clients.
+const ERROR_DOMAIN: &str = "trajectory.orbitalik";
+
 impl From<PropagationError> for tonic::Status {
     fn from(value: PropagationError) -> Self {
+        let message = value.to_string();
+
         match value {
             PropagationError::TleFetch(status) => status,
-            PropagationError::TleParse(_)
-            | PropagationError::ElementsCreation(_)
-            | PropagationError::DatetimeToMinutesSinceEpochFailed(_)
-            | PropagationError::PropagationFailed(_) => {
-                tracing::error!("propagation error: {:?}", value);
+            PropagationError::InvalidElementSet(error) => {
+                let mut details =
+                    ErrorDetails::with_error_info(error.reason(), ERROR_DOMAIN, HashMap::new());
+                details.add_bad_request_violation("identifier.inline", error.to_string());
+                Self::with_error_details(Code::InvalidArgument, message, details)
+            }
+            PropagationError::ElementsCreation(_) => error_status(
+                Code::FailedPrecondition,
+                message,
+                "INVALID_EPOCH_ELEMENTS",
+                [],
+            ),
+            PropagationError::DatetimeToMinutesSinceEpochFailed(_) => {
+                error_status(Code::OutOfRange, message, "DATETIME_OUT_OF_RANGE", [])
+            }
+            PropagationError::PropagationFailed(error) => sgp4_error_status(&error, message),
+            PropagationError::TleTooOld {
+                epoch,
+                age_days,
+                max_days,
+                regime,
+            } => error_status(
+                Code::FailedPrecondition,
+                message,
+                "TLE_TOO_OLD",
+                [
+                    ("tle_epoch", epoch.to_rfc3339()),
+                    ("age_days", format!("{age_days:.3}")),
+                    ("max_age_days", format!("{max_days:.3}")),
+                    ("orbit_regime", regime.to_string()),
+                ],
+            ),
+            PropagationError::Reentered {
+                minutes_since_epoch,
+            } => error_status(
+                Code::FailedPrecondition,
+                message,
+                "SATELLITE_DECAYED",
+                [("minutes_since_epoch", minutes_since_epoch.to_string())],
+            ),
+            PropagationError::IntegrationSpanExceeded { max_days, .. } => error_status(
+                Code::OutOfRange,
+                message,
+                "INTEGRATION_SPAN_EXCEEDED",
+                [("max_days", format!("{max_days:.3}"))],
+            ),
+            PropagationError::EphemerisNotFound(id) => error_status(
+                Code::NotFound,
+                message,
+                "EPHEMERIS_NOT_FOUND",
+                [("ephemeris_id", id)],
+            ),
+            PropagationError::EphemerisNotSupported => {
+                let mut details = ErrorDetails::with_error_info(
+                    "UNSUPPORTED_IDENTIFIER",
+                    ERROR_DOMAIN,
+                    HashMap::new(),
+                );
+                details.add_bad_request_violation("identifier.ephemeris_id", message.clone());
+                Self::with_error_details(Code::InvalidArgument, message, details)
+            }
+            PropagationError::PropagatorNotSupported(_) => {
+                let mut details = ErrorDetails::with_error_info(
+                    "UNSUPPORTED_PROPAGATOR",
+                    ERROR_DOMAIN,
+                    HashMap::new(),
+                );
+                details.add_bad_request_violation("propagator", message.clone());
+                Self::with_error_details(Code::InvalidArgument, message, details)
+            }
+            PropagationError::OutsideEphemerisCoverage {
+                datetime,
+                start,
+                stop,
+            } => error_status(
+                Code::OutOfRange,
+                message,
+                "OUTSIDE_EPHEMERIS_COVERAGE",
+                [
+                    ("requested_time", datetime.to_rfc3339()),
+                    ("coverage_start", start.to_rfc3339()),
+                    ("coverage_stop", stop.to_rfc3339()),
+                ],
+            ),
+            error @ (PropagationError::TleParse(_)
+            | PropagationError::TaskFailed(_)
+            | PropagationError::IntegrationFailed { .. }) => {
+                tracing::error!("propagation error: {:?}", error);
                 Self::internal("Internal server error")
             }
         }
     }
 }
 
+/// Status of an SGP4 failure, `message` being the description of the error.
+fn sgp4_error_status(error: &sgp4::Error, message: String) -> tonic::Status {
+    match *error {
+        sgp4::Error::NegativeSemiLatusRectum { t } => error_status(
+            Code::FailedPrecondition,
+            format!(
+                "Satellite has decayed: SGP4 found a negative semi-latus rectum \
+                     {t:.1} minutes after the TLE epoch"
+            ),
+            "SATELLITE_DECAYED",
+            [("minutes_since_epoch", t.to_string())],
+        ),
+        sgp4::Error::OutOfRangeEccentricity { eccentricity, t }
+        | sgp4::Error::OutOfRangePerturbedEccentricity { eccentricity, t } => error_status(
+            Code::OutOfRange,
+            message,
+            "ECCENTRICITY_OUT_OF_RANGE",
+            [
+                ("eccentricity", eccentricity.to_string()),
+                ("minutes_since_epoch", t.to_string()),
+            ],
+        ),
+    }
+}
+
+fn error_status<const N: usize>(
+    code: Code,
+    message: String,
+    reason: &str,
+    metadata: [(&str, String); N],
+) -> tonic::Status {
+    let metadata: HashMap<String, String> = metadata
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+
+    tonic::Status::with_error_details(
+        code,
+        message,
+        ErrorDetails::with_error_info(reason, ERROR_DOMAIN, metadata),
+    )
+}
+
+#[derive(Debug, Error)]
+pub enum ElementSetError {
+    #[error("Invalid TLE: {0}")]
+    Tle(#[from] sgp4::TleError),
+    #[error("Invalid TLE: {0}")]
+    TleFormat(&'static str),
+    #[error("Invalid OMM JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Malformed OMM: {0}")]
+    Malformed(&'static str),
+    #[error("Expected a single OMM object, found several in {0}")]
+    MultipleObjects(String),
+    #[error("Missing OMM field {0}")]
+    MissingField(&'static str),
+    #[error("Invalid value '{1}' for {0}")]
+    InvalidField(&'static str, String),
+    #[error("Unsupported {0} '{1}'")]
+    Unsupported(&'static str, String),
+    #[error("{0} does not fit in a TLE")]
+    NotRepresentable(&'static str),
+    #[error("Invalid elements: {0}")]
+    Elements(#[from] sgp4::ElementsError),
+}
+
+impl ElementSetError {
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::NotRepresentable(_) => "NOT_REPRESENTABLE_AS_TLE",
+            Self::Elements(_) => "INVALID_EPOCH_ELEMENTS",
+            _ => "INVALID_ELEMENT_SET",
+        }
+    }
+}
+
+impl From<ElementSetError> for tonic::Status {
+    fn from(value: ElementSetError) -> Self {
+        error_status(Code::InvalidArgument, value.to_string(), value.reason(), [])
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum EphemerisError {
+    #[error("Malformed OEM: {0}")]
+    Malformed(&'static str),
+    #[error("Expected a single OEM segment, found several")]
+    MultipleSegments,
+    #[error("Missing OEM field {0}")]
+    MissingField(&'static str),
+    #[error("Invalid value '{1}' for {0}")]
+    InvalidField(&'static str, String),
+    #[error("Unsupported {0} '{1}'")]
+    Unsupported(&'static str, String),
+    #[error(
+        "{method} interpolation of degree {degree} needs at least {required} states, \
+         found {found}"
+    )]
+    TooFewStates {
+        method: InterpolationMethod,
+        degree: usize,
+        required: usize,
+        found: usize,
+    },
+    #[error("States are not in strictly increasing epoch order at {0}")]
+    UnorderedStates(DateTime<Utc>),
+    #[error("Ephemeris has {found} states, more than the maximum of {max}")]
+    TooManyStates { found: usize, max: usize },
+    #[error("The ephemeris store is full, with {0} ephemerides")]
+    StoreFull(usize),
+}
+
+impl From<EphemerisError> for tonic::Status {
+    fn from(value: EphemerisError) -> Self {
+        let (code, reason) = match value {
+            EphemerisError::StoreFull(_) => (Code::ResourceExhausted, "EPHEMERIS_STORE_FULL"),
+            _ => (Code::InvalidArgument, "INVALID_EPHEMERIS"),
+        };
+        error_status(code, value.to_string(), reason, [])
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum TimestampConversionError {
     #[error("Failed to convert nanos: {0}")]
     NanosOutOfRange(#[from] std::num::TryFromIntError),
     #[error("Failed to convert prost_types::Timestamp to chrono::DateTime<Utc>")]
     InvalidTimestamp,
+    #[error("Failed to convert prost_types::Duration to chrono::TimeDelta")]
+    InvalidDuration,
 }
 
 impl From<TimestampConversionError> for tonic::Status {
     fn from(value: TimestampConversionError) -> Self {
-        tracing::error!("timestamp conversion failed: {:?}", value);
-        Self::internal("Internal server error")
+        error_status(
+            Code::InvalidArgument,
+            value.to_string(),
+            "INVALID_TIMESTAMP",
+            [],
+        )
     }
 }
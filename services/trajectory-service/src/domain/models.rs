@@ -1,7 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use std::fmt;
 use std::fmt::Formatter;
 
+use crate::astro::models::PropagationMethod;
+use crate::astro::omm::OmmFormat;
+
 #[derive(Debug, Clone)]
 pub enum SatelliteIdentifier {
     NoradId(u32),
@@ -17,11 +20,68 @@ impl fmt::Display for SatelliteIdentifier {
     }
 }
 
+/// Element set supplied with a request.
+#[derive(Debug, Clone)]
+pub enum InlineElementSet {
+    Tle {
+        satellite_name: Option<String>,
+        line1: String,
+        line2: String,
+    },
+    /// CCSDS OMM document.
+    Omm(OmmFormat, String),
+}
+
+/// Which of the stored epochs of a catalog satellite to use, relative to the time a
+/// request propagates to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TleSelection {
+    #[default]
+    Latest,
+    /// Epoch closest to the requested time, before or after it.
+    Nearest,
+    /// Latest epoch not after the requested time.
+    LatestBefore,
+}
+
+/// Where the element set of a request comes from.
+#[derive(Debug, Clone)]
+pub enum ElementSetSource {
+    Catalog(SatelliteIdentifier, TleSelection),
+    Inline(InlineElementSet),
+    /// Identifier of a stored ephemeris.
+    Ephemeris(String),
+}
+
+impl fmt::Display for ElementSetSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Catalog(identifier, _) => identifier.fmt(f),
+            Self::Inline(_) => write!(f, "Inline element set"),
+            Self::Ephemeris(id) => write!(f, "Ephemeris '{id}'"),
+        }
+    }
+}
+
 pub struct ComputationMetadata {
-    pub propagation_model: String,
+    pub propagation: PropagationMethod,
     pub computation_time: DateTime<Utc>,
     pub norad_id: u32,
     pub satellite_name: String,
-    pub tle_epoch: DateTime<Utc>,
+    /// Unset for ephemerides.
+    pub tle_epoch: Option<DateTime<Utc>>,
+    /// Earth orientation parameters were not available for the computation times,
+    /// so UT1 = UTC and no polar motion were assumed.
+    pub eop_degraded: bool,
+    /// The element set came with the request rather than from the TLE catalog.
+    pub inline_element_set: bool,
+    /// Non-fatal problems with the result, such as a TLE past its warning age.
+    pub warnings: Vec<String>,
 }
 
+impl ComputationMetadata {
+    /// How far from the TLE epoch the computation propagated, negative when backwards.
+    pub fn propagation_offset(&self) -> Option<TimeDelta> {
+        self.tle_epoch.map(|epoch| self.computation_time - epoch)
+    }
+}
@@ -5,6 +5,21 @@ pub struct AppConfig {
     pub http_port: u16,
     pub grpc_port: u16,
     pub tle_service_address: String,
+    pub track_tle_refresh_secs: u64,
+    pub tle_cache_ttl_secs: u64,
+    pub tle_cache_max_entries: usize,
+    pub propagator_cache_max_entries: usize,
+    pub eop_file_path: Option<String>,
+    /// Directory of CCSDS OEM files (`*.oem`) loaded as ephemerides at startup.
+    pub ephemeris_dir: Option<String>,
+    /// Bounds on the ephemerides held at once and on the states of each.
+    pub ephemeris_max_entries: usize,
+    pub ephemeris_max_states: usize,
+    /// TLE age limits in days, as (warning, maximum), per orbit regime.
+    pub tle_age_leo_days: (f64, f64),
+    pub tle_age_meo_days: (f64, f64),
+    pub tle_age_geo_days: (f64, f64),
+    pub tle_age_heo_days: (f64, f64),
 }
 
 impl AppConfig {
@@ -18,6 +33,34 @@ impl AppConfig {
                 "TLE_SERVICE_ADDRESS",
                 "grpc://tle-ingestion-service:50051",
             ),
+            track_tle_refresh_secs: env_u64("TRACK_TLE_REFRESH_SECS", 600),
+            tle_cache_ttl_secs: env_u64("TLE_CACHE_TTL_SECS", 3600),
+            tle_cache_max_entries: env_usize("TLE_CACHE_MAX_ENTRIES", 20_000),
+            propagator_cache_max_entries: env_usize("PROPAGATOR_CACHE_MAX_ENTRIES", 20_000),
+            eop_file_path: std::env::var("EOP_FILE_PATH")
+                .ok()
+                .filter(|path| !path.is_empty()),
+            ephemeris_dir: std::env::var("EPHEMERIS_DIR")
+                .ok()
+                .filter(|path| !path.is_empty()),
+            ephemeris_max_entries: env_usize("EPHEMERIS_MAX_ENTRIES", 1_000),
+            ephemeris_max_states: env_usize("EPHEMERIS_MAX_STATES", 100_000),
+            tle_age_leo_days: (
+                env_days("TLE_AGE_LEO_WARNING_DAYS", 3.0),
+                env_days("TLE_AGE_LEO_MAX_DAYS", 14.0),
+            ),
+            tle_age_meo_days: (
+                env_days("TLE_AGE_MEO_WARNING_DAYS", 14.0),
+                env_days("TLE_AGE_MEO_MAX_DAYS", 60.0),
+            ),
+            tle_age_geo_days: (
+                env_days("TLE_AGE_GEO_WARNING_DAYS", 30.0),
+                env_days("TLE_AGE_GEO_MAX_DAYS", 120.0),
+            ),
+            tle_age_heo_days: (
+                env_days("TLE_AGE_HEO_WARNING_DAYS", 7.0),
+                env_days("TLE_AGE_HEO_MAX_DAYS", 30.0),
+            ),
         }
     }
 }
@@ -32,3 +75,25 @@ fn env_u16(key: &str, default: u16) -> u16 {
         .and_then(|v| v.parse::<u16>().ok())
         .unwrap_or(default)
 }
+
+fn env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(default)
+}
+
+fn env_usize(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(default)
+}
+
+fn env_days(key: &str, default: f64) -> f64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|days| days.is_finite() && *days >= 0.0)
+        .unwrap_or(default)
+}
@@ -13,12 +13,25 @@
 #![warn(missing_docs, clippy::all, clippy::pedantic, clippy::nursery)]
 #![allow(clippy::suboptimal_flops)]
 
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::astro::eop::EopTable;
 use crate::domain::errors::StartupError;
+use crate::service::eclipses::EclipsesService;
+use crate::service::ephemeris::EphemerisService;
+use crate::service::ephemeris_store::EphemerisStore;
 use crate::service::look_angles::LookAnglesService;
+use crate::service::orbital_elements::OrbitalElementsService;
+use crate::service::passes::PassesService;
 use crate::service::position::PositionService;
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::{TleAgeLimits, TleAgePolicy};
+use crate::service::tracking::TrackingService;
+use crate::transport::adapter::tle_cache::{TleCache, TleCacheConfig};
 use crate::transport::adapter::tle_client::TleGrpcClient;
+use crate::transport::grpc::trajectory::TrajectoryGrpcServer;
 
 mod astro;
 mod domain;
@@ -32,18 +45,94 @@ async fn main() -> Result<(), StartupError> {
 
     infrastructure::logger::init_logger(&config.app_env)?;
 
+    let tle_cache = Arc::new(
+        TleCache::new(TleCacheConfig {
+            ttl: Duration::from_secs(config.tle_cache_ttl_secs),
+            max_entries: config.tle_cache_max_entries,
+        })
+        .map_err(StartupError::from)?,
+    );
+
     let tle_grpc_client = Arc::new(
-        TleGrpcClient::new(config.tle_service_address)
+        TleGrpcClient::new(config.tle_service_address, tle_cache.clone())
             .await
             .map_err(StartupError::from)?,
     );
 
-    let position_service = PositionService::new(tle_grpc_client.clone());
-    let look_angles_service = LookAnglesService::new(tle_grpc_client.clone());
+    let propagator_cache = Arc::new(
+        PropagatorCache::new(config.propagator_cache_max_entries).map_err(StartupError::from)?,
+    );
+
+    let eop = Arc::new(load_eop(config.eop_file_path.as_deref())?);
+    let ephemeris_store = Arc::new(load_ephemerides(
+        EphemerisStore::new(config.ephemeris_max_entries, config.ephemeris_max_states),
+        config.ephemeris_dir.as_deref(),
+    )?);
+
+    let age_policy = Arc::new(TleAgePolicy {
+        leo: TleAgeLimits::from_days(config.tle_age_leo_days.0, config.tle_age_leo_days.1),
+        meo: TleAgeLimits::from_days(config.tle_age_meo_days.0, config.tle_age_meo_days.1),
+        geo: TleAgeLimits::from_days(config.tle_age_geo_days.0, config.tle_age_geo_days.1),
+        heo: TleAgeLimits::from_days(config.tle_age_heo_days.0, config.tle_age_heo_days.1),
+    });
 
-    let http_server = transport::http::server::run(config.http_port)?;
-    let grpc_server =
-        transport::grpc::server::run(config.grpc_port, position_service, look_angles_service);
+    let position_service = PositionService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        ephemeris_store.clone(),
+        age_policy.clone(),
+        eop.clone(),
+    );
+    let look_angles_service = LookAnglesService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        ephemeris_store.clone(),
+        age_policy.clone(),
+        eop.clone(),
+    );
+    let passes_service = PassesService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        age_policy.clone(),
+        eop.clone(),
+    );
+    let ephemeris_service = EphemerisService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        age_policy.clone(),
+        eop.clone(),
+    );
+    let tracking_service = TrackingService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        age_policy.clone(),
+        eop.clone(),
+        Duration::from_secs(config.track_tle_refresh_secs),
+    );
+    let eclipses_service = EclipsesService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        age_policy.clone(),
+    );
+    let orbital_elements_service = OrbitalElementsService::new(
+        tle_grpc_client.clone(),
+        propagator_cache.clone(),
+        age_policy,
+    );
+
+    let http_server = transport::http::server::run(config.http_port, tle_cache, propagator_cache)?;
+    let trajectory_service = TrajectoryGrpcServer::new(
+        position_service,
+        look_angles_service,
+        passes_service,
+        ephemeris_service,
+        tracking_service,
+        eclipses_service,
+        orbital_elements_service,
+        ephemeris_store,
+        eop,
+    );
+    let grpc_server = transport::grpc::server::run(config.grpc_port, trajectory_service);
 
     tokio::try_join!(
         async { http_server.await.map_err(StartupError::from) },
@@ -52,3 +141,41 @@ async fn main() -> Result<(), StartupError> {
 
     Ok(())
 }
+
+/// Read the IERS Earth orientation file, if one is configured.
+///
+/// Without a file every computation falls back to UT1 = UTC and no polar motion.
+fn load_eop(path: Option<&str>) -> Result<EopTable, StartupError> {
+    let Some(path) = path else {
+        tracing::warn!("EOP_FILE_PATH not set, Earth orientation parameters are disabled");
+        return Ok(EopTable::empty());
+    };
+
+    let text =
+        std::fs::read_to_string(path).map_err(|e| StartupError::EopFile(path.to_string(), e))?;
+    let eop = EopTable::parse_finals(&text);
+    if eop.len() == 0 {
+        return Err(StartupError::EopEmpty(path.to_string()));
+    }
+    tracing::info!(
+        "loaded {} days of Earth orientation parameters from {path}",
+        eop.len()
+    );
+
+    Ok(eop)
+}
+
+/// Load the OEM files of the ephemeris directory into `store`, if one is configured.
+fn load_ephemerides(
+    store: EphemerisStore,
+    dir: Option<&str>,
+) -> Result<EphemerisStore, StartupError> {
+    let Some(dir) = dir else {
+        return Ok(store);
+    };
+
+    let loaded = store.load_dir(Path::new(dir))?;
+    tracing::info!("loaded {loaded} ephemerides from {dir}");
+
+    Ok(store)
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::astro::models::Eclipse;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ComputationMetadata, ElementSetSource};
+use crate::service::element_sets::{PropagationOptions, ResolvedElements};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+pub struct EclipsesService {
+    tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    age_policy: Arc<TleAgePolicy>,
+}
+
+impl EclipsesService {
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        age_policy: Arc<TleAgePolicy>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            age_policy,
+        }
+    }
+
+    pub async fn get_eclipses_with_metadata(
+        &self,
+        source: ElementSetSource,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        options: PropagationOptions,
+    ) -> Result<(Vec<Eclipse>, ComputationMetadata), PropagationError> {
+        let elements = ResolvedElements::resolve(
+            &self.tle_grpc_client,
+            &self.propagator_cache,
+            source,
+            start,
+            options.propagator,
+        )
+        .await?;
+        let warnings = self
+            .age_policy
+            .check(&elements, options.age_limits, start, end)?;
+
+        // a month-long scan with Sun positions and root finding, off the executor
+        let propagator = elements.propagator.clone();
+        let eclipses =
+            tokio::task::spawn_blocking(move || propagator.eclipses_between(start, end)).await??;
+        let metadata = ComputationMetadata {
+            propagation: elements.propagator.method(),
+            computation_time: start,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: false,
+            inline_element_set: elements.inline,
+            warnings,
+        };
+
+        Ok((eclipses, metadata))
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::astro::element_set::ElementSet;
+use crate::astro::models::{PropagatorKind, Tle};
+use crate::astro::propagator::Propagator;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ElementSetSource, InlineElementSet};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgeOverrides;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+/// How a request wants its element set propagated.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PropagationOptions {
+    pub propagator: PropagatorKind,
+    pub age_limits: TleAgeOverrides,
+}
+
+/// Element set found for a request, before its propagator is built.
+pub enum FetchedElements {
+    Catalog(Tle),
+    Inline(InlineElementSet),
+}
+
+impl FetchedElements {
+    /// Fetch the catalog TLE selected relative to `at`, or take the inline element set
+    /// as is.
+    pub async fn fetch(
+        tle_grpc_client: &TleGrpcClient,
+        source: ElementSetSource,
+        at: DateTime<Utc>,
+    ) -> Result<Self, PropagationError> {
+        match source {
+            ElementSetSource::Catalog(identifier, selection) => Ok(Self::Catalog(
+                tle_grpc_client
+                    .get_tle_at(identifier, at, selection)
+                    .await?,
+            )),
+            ElementSetSource::Inline(elements) => Ok(Self::Inline(elements)),
+            ElementSetSource::Ephemeris(_) => Err(PropagationError::EphemerisNotSupported),
+        }
+    }
+
+    /// Build the propagator, through the cache for catalog TLEs only: an inline element
+    /// set may reuse the NORAD ID and epoch of a catalog one with different elements.
+    pub fn build(
+        &self,
+        propagator_cache: &PropagatorCache,
+        kind: PropagatorKind,
+    ) -> Result<ResolvedElements, PropagationError> {
+        match self {
+            Self::Catalog(tle) => Ok(ResolvedElements {
+                propagator: propagator_cache.get_or_build(tle, kind)?,
+                norad_id: tle.norad_id,
+                satellite_name: tle.satellite_name.clone(),
+                epoch: Some(tle.epoch),
+                inline: false,
+            }),
+            Self::Inline(elements) => ResolvedElements::from_inline(elements, kind),
+        }
+    }
+}
+
+/// Propagator of a request with the element set details reported in its metadata.
+pub struct ResolvedElements {
+    pub propagator: Arc<Propagator>,
+    pub norad_id: u32,
+    pub satellite_name: String,
+    /// Unset for ephemerides, which have no single epoch.
+    pub epoch: Option<DateTime<Utc>>,
+    pub inline: bool,
+}
+
+impl ResolvedElements {
+    pub async fn resolve(
+        tle_grpc_client: &TleGrpcClient,
+        propagator_cache: &PropagatorCache,
+        source: ElementSetSource,
+        at: DateTime<Utc>,
+        kind: PropagatorKind,
+    ) -> Result<Self, PropagationError> {
+        FetchedElements::fetch(tle_grpc_client, source, at)
+            .await?
+            .build(propagator_cache, kind)
+    }
+
+    fn from_inline(
+        elements: &InlineElementSet,
+        kind: PropagatorKind,
+    ) -> Result<Self, PropagationError> {
+        let element_set = match elements {
+            InlineElementSet::Tle {
+                satellite_name,
+                line1,
+                line2,
+            } => ElementSet::from_tle(satellite_name.clone(), line1, line2)?,
+            InlineElementSet::Omm(format, document) => ElementSet::from_omm(document, *format)?,
+        };
+
+        // elements SGP4 cannot initialise from are the client's to fix here
+        let propagator = Propagator::from_element_set(&element_set, kind).map_err(|e| match e {
+            PropagationError::ElementsCreation(e) => PropagationError::InvalidElementSet(e.into()),
+            e => e,
+        })?;
+
+        Ok(Self {
+            propagator: Arc::new(propagator),
+            norad_id: element_set.norad_id,
+            satellite_name: element_set.object_name.unwrap_or_default(),
+            epoch: Some(element_set.epoch),
+            inline: true,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::astro::eop::EopTable;
+use crate::astro::models::SatellitePosition;
+use crate::astro::position::PositionComputation;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ComputationMetadata, ElementSetSource};
+use crate::service::element_sets::{PropagationOptions, ResolvedElements};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+pub struct EphemerisService {
+    tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    age_policy: Arc<TleAgePolicy>,
+    eop: Arc<EopTable>,
+}
+
+impl EphemerisService {
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        age_policy: Arc<TleAgePolicy>,
+        eop: Arc<EopTable>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            age_policy,
+            eop,
+        }
+    }
+
+    /// Propagate one TLE to every requested instant, reusing a single propagator.
+    pub async fn get_ephemeris_with_metadata(
+        &self,
+        source: ElementSetSource,
+        datetimes: &[DateTime<Utc>],
+        compute: PositionComputation,
+        options: PropagationOptions,
+    ) -> Result<(Vec<SatellitePosition>, ComputationMetadata), PropagationError> {
+        let computation_time = datetimes.first().copied().unwrap_or_else(Utc::now);
+        let elements = ResolvedElements::resolve(
+            &self.tle_grpc_client,
+            &self.propagator_cache,
+            source,
+            computation_time,
+            options.propagator,
+        )
+        .await?;
+        let warnings = self.age_policy.check(
+            &elements,
+            options.age_limits,
+            datetimes.iter().min().copied().unwrap_or(computation_time),
+            datetimes.iter().max().copied().unwrap_or(computation_time),
+        )?;
+
+        // up to MAX_EPHEMERIS_SAMPLES propagations, numerical ones with COWELL
+        let propagator = elements.propagator.clone();
+        let eop = self.eop.clone();
+        let times = datetimes.to_vec();
+        let samples = tokio::task::spawn_blocking(move || {
+            times
+                .into_iter()
+                .map(|datetime| propagator.position_at(datetime, &compute, &eop))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .await??;
+
+        let metadata = ComputationMetadata {
+            propagation: elements.propagator.method(),
+            computation_time,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: !datetimes.iter().all(|&datetime| self.eop.covers(datetime)),
+            inline_element_set: elements.inline,
+            warnings,
+        };
+
+        Ok((samples, metadata))
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::astro::interpolation::Ephemeris;
+use crate::astro::models::PropagatorKind;
+use crate::astro::propagator::Propagator;
+use crate::domain::errors::{EphemerisError, PropagationError, StartupError};
+use crate::service::element_sets::ResolvedElements;
+
+struct StoredEphemeris {
+    propagator: Arc<Propagator>,
+    norad_id: u32,
+    satellite_name: String,
+}
+
+/// Operator-provided ephemerides, keyed by the identifier requests refer to them with.
+///
+/// Ephemerides are loaded from a directory at startup or uploaded at run time, an
+/// upload replacing any ephemeris stored under the same identifier. Both the number
+/// of ephemerides and the states of each are bounded, and anything past the bounds is
+/// rejected rather than evicting ephemerides operators rely on.
+pub struct EphemerisStore {
+    max_entries: usize,
+    max_states: usize,
+    entries: RwLock<HashMap<String, StoredEphemeris>>,
+}
+
+impl EphemerisStore {
+    pub fn new(max_entries: usize, max_states: usize) -> Self {
+        Self {
+            max_entries,
+            max_states,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Load every `*.oem` file of `dir`, keyed by its file name without the extension.
+    pub fn load_dir(&self, dir: &Path) -> Result<usize, StartupError> {
+        let display = || dir.display().to_string();
+        let mut loaded = 0;
+
+        for entry in std::fs::read_dir(dir).map_err(|e| StartupError::EphemerisDir(display(), e))? {
+            let path = entry
+                .map_err(|e| StartupError::EphemerisDir(display(), e))?
+                .path();
+            let (Some(id), Some("oem")) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.extension().and_then(|extension| extension.to_str()),
+            ) else {
+                continue;
+            };
+
+            let file = path.display().to_string();
+            let document = std::fs::read_to_string(&path)
+                .map_err(|e| StartupError::EphemerisRead(file.clone(), e))?;
+            let ephemeris = Ephemeris::from_oem(&document)
+                .map_err(|e| StartupError::EphemerisParse(file.clone(), e))?;
+
+            self.insert(id.to_string(), ephemeris)
+                .map_err(|e| StartupError::EphemerisParse(file, e))?;
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
+    pub fn insert(&self, id: String, ephemeris: Ephemeris) -> Result<(), EphemerisError> {
+        if ephemeris.len() > self.max_states {
+            return Err(EphemerisError::TooManyStates {
+                found: ephemeris.len(),
+                max: self.max_states,
+            });
+        }
+
+        let entry = StoredEphemeris {
+            norad_id: ephemeris.norad_id.unwrap_or_default(),
+            satellite_name: ephemeris.object_name.clone().unwrap_or_else(|| id.clone()),
+            propagator: Arc::new(Propagator::from_ephemeris(ephemeris)),
+        };
+
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= self.max_entries && !entries.contains_key(&id) {
+            return Err(EphemerisError::StoreFull(entries.len()));
+        }
+        entries.insert(id, entry);
+        drop(entries);
+
+        Ok(())
+    }
+
+    /// Ephemerides are only interpolated, so any propagator but the default is refused
+    /// rather than silently ignored.
+    pub fn resolve(
+        &self,
+        id: &str,
+        kind: PropagatorKind,
+    ) -> Result<ResolvedElements, PropagationError> {
+        if kind != PropagatorKind::default() {
+            return Err(PropagationError::PropagatorNotSupported(kind));
+        }
+
+        self.entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .map(|entry| ResolvedElements {
+                propagator: entry.propagator.clone(),
+                norad_id: entry.norad_id,
+                satellite_name: entry.satellite_name.clone(),
+                epoch: None,
+                inline: false,
+            })
+            .ok_or_else(|| PropagationError::EphemerisNotFound(id.to_string()))
+    }
+}
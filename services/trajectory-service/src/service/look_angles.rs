@@ -2,42 +2,84 @@ use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
 use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
 use crate::astro::look_angles::LookAnglesComputation;
 use crate::astro::models::LookAngles;
-use crate::astro::propagator::Propagator;
 use crate::domain::errors::PropagationError;
-use crate::domain::models::{ComputationMetadata, SatelliteIdentifier};
+use crate::domain::models::{ComputationMetadata, ElementSetSource};
+use crate::service::element_sets::{PropagationOptions, ResolvedElements};
+use crate::service::ephemeris_store::EphemerisStore;
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
 use crate::transport::adapter::tle_client::TleGrpcClient;
 
 pub struct LookAnglesService {
     tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    ephemeris_store: Arc<EphemerisStore>,
+    age_policy: Arc<TleAgePolicy>,
+    eop: Arc<EopTable>,
 }
 
 impl LookAnglesService {
-    pub const fn new(tle_grpc_client: Arc<TleGrpcClient>) -> Self {
-        Self { tle_grpc_client }
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        ephemeris_store: Arc<EphemerisStore>,
+        age_policy: Arc<TleAgePolicy>,
+        eop: Arc<EopTable>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            ephemeris_store,
+            age_policy,
+            eop,
+        }
     }
 
     pub async fn get_look_angles_with_metadata(
         &self,
-        satellite_identifier: SatelliteIdentifier,
+        source: ElementSetSource,
         datetime: DateTime<Utc>,
-        observer: &Geodetic,
-        compute: &LookAnglesComputation,
+        observer: Geodetic,
+        compute: LookAnglesComputation,
+        options: PropagationOptions,
     ) -> Result<(LookAngles, ComputationMetadata), PropagationError> {
-        let tle = self
-            .tle_grpc_client
-            .get_tle(satellite_identifier.clone())
-            .await?;
+        let elements = match source {
+            ElementSetSource::Ephemeris(id) => {
+                self.ephemeris_store.resolve(&id, options.propagator)?
+            }
+            source => {
+                ResolvedElements::resolve(
+                    &self.tle_grpc_client,
+                    &self.propagator_cache,
+                    source,
+                    datetime,
+                    options.propagator,
+                )
+                .await?
+            }
+        };
+        let warnings = self
+            .age_policy
+            .check(&elements, options.age_limits, datetime, datetime)?;
 
-        let look_angles =
-            Propagator::from_tle(&tle)?.look_angles_at(datetime, observer, compute)?;
+        let propagator = elements.propagator.clone();
+        let eop = self.eop.clone();
+        let look_angles = tokio::task::spawn_blocking(move || {
+            propagator.look_angles_at(datetime, &observer, &compute, &eop)
+        })
+        .await??;
         let metadata = ComputationMetadata {
-            propagation_model: "SGP4".to_string(),
+            propagation: elements.propagator.method(),
             computation_time: datetime,
-            norad_id: tle.norad_id,
-            satellite_name: tle.satellite_name,
-            tle_epoch: tle.epoch,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: !self.eop.covers(datetime),
+            inline_element_set: elements.inline,
+            warnings,
         };
 
         Ok((look_angles, metadata))
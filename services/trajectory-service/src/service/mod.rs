@@ -1,2 +1,11 @@
+pub mod eclipses;
+pub mod element_sets;
+pub mod ephemeris;
+pub mod ephemeris_store;
 pub mod look_angles;
+pub mod orbital_elements;
+pub mod passes;
 pub mod position;
+pub mod propagator_cache;
+pub mod tle_age;
+pub mod tracking;
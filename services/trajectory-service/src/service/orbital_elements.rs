@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::astro::models::OrbitalElements;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ComputationMetadata, ElementSetSource};
+use crate::service::element_sets::{PropagationOptions, ResolvedElements};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+pub struct OrbitalElementsService {
+    tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    age_policy: Arc<TleAgePolicy>,
+}
+
+impl OrbitalElementsService {
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        age_policy: Arc<TleAgePolicy>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            age_policy,
+        }
+    }
+
+    /// Elements of the satellite's TLE, with osculating elements at `datetime` or at
+    /// the TLE epoch when `None`.
+    pub async fn get_orbital_elements_with_metadata(
+        &self,
+        source: ElementSetSource,
+        datetime: Option<DateTime<Utc>>,
+        options: PropagationOptions,
+    ) -> Result<(OrbitalElements, ComputationMetadata), PropagationError> {
+        // without a datetime the elements are reported at the epoch of the selected TLE,
+        // so select relative to now
+        let elements = ResolvedElements::resolve(
+            &self.tle_grpc_client,
+            &self.propagator_cache,
+            source,
+            datetime.unwrap_or_else(Utc::now),
+            options.propagator,
+        )
+        .await?;
+
+        let datetime = datetime
+            .or(elements.epoch)
+            .ok_or(PropagationError::EphemerisNotSupported)?;
+        let warnings = self
+            .age_policy
+            .check(&elements, options.age_limits, datetime, datetime)?;
+        let propagator = elements.propagator.clone();
+        let orbital_elements =
+            tokio::task::spawn_blocking(move || propagator.orbital_elements_at(datetime)).await??;
+        let metadata = ComputationMetadata {
+            propagation: elements.propagator.method(),
+            computation_time: datetime,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: false,
+            inline_element_set: elements.inline,
+            warnings,
+        };
+
+        Ok((orbital_elements, metadata))
+    }
+}
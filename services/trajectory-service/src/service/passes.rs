@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
+use crate::astro::models::Pass;
+use crate::astro::passes::PassCriteria;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ComputationMetadata, ElementSetSource};
+use crate::service::element_sets::{PropagationOptions, ResolvedElements};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+pub struct PassesService {
+    tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    age_policy: Arc<TleAgePolicy>,
+    eop: Arc<EopTable>,
+}
+
+impl PassesService {
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        age_policy: Arc<TleAgePolicy>,
+        eop: Arc<EopTable>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            age_policy,
+            eop,
+        }
+    }
+
+    /// Predict passes, optionally keeping only the optically visible ones.
+    ///
+    /// With `criteria.max_sun_elevation` set, every pass is annotated with its visible
+    /// segments and passes without any are dropped.
+    pub async fn predict_passes_with_metadata(
+        &self,
+        source: ElementSetSource,
+        observer: Geodetic,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        criteria: PassCriteria,
+        options: PropagationOptions,
+    ) -> Result<(Vec<Pass>, ComputationMetadata), PropagationError> {
+        let elements = ResolvedElements::resolve(
+            &self.tle_grpc_client,
+            &self.propagator_cache,
+            source,
+            start,
+            options.propagator,
+        )
+        .await?;
+        let warnings = self
+            .age_policy
+            .check(&elements, options.age_limits, start, end)?;
+
+        // the search propagates thousands of times over a long window, off the executor
+        let propagator = elements.propagator.clone();
+        let eop = self.eop.clone();
+        let passes = tokio::task::spawn_blocking(move || {
+            let atmosphere = criteria.atmosphere.as_ref();
+            let mut passes = propagator.passes_between(
+                &observer,
+                start,
+                end,
+                criteria.min_elevation,
+                atmosphere,
+                &eop,
+            )?;
+
+            if let Some(max_sun_elevation) = criteria.max_sun_elevation {
+                for pass in &mut passes {
+                    pass.visible_segments = propagator.visible_segments(
+                        pass,
+                        &observer,
+                        max_sun_elevation,
+                        atmosphere,
+                        &eop,
+                    )?;
+                }
+                passes.retain(|pass| !pass.visible_segments.is_empty());
+            }
+
+            Ok::<_, PropagationError>(passes)
+        })
+        .await??;
+
+        let metadata = ComputationMetadata {
+            propagation: elements.propagator.method(),
+            computation_time: start,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: !(self.eop.covers(start) && self.eop.covers(end)),
+            inline_element_set: elements.inline,
+            warnings,
+        };
+
+        Ok((passes, metadata))
+    }
+}
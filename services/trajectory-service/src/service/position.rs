@@ -1,42 +1,247 @@
 use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::task::JoinSet;
 
-use crate::astro::models::SatellitePosition;
+use crate::astro::eop::EopTable;
+use crate::astro::models::{SatellitePosition, Tle};
 use crate::astro::position::PositionComputation;
-use crate::astro::propagator::Propagator;
 use crate::domain::errors::PropagationError;
-use crate::domain::models::{ComputationMetadata, SatelliteIdentifier};
+use crate::domain::models::{
+    ComputationMetadata, ElementSetSource, SatelliteIdentifier, TleSelection,
+};
+use crate::service::element_sets::{FetchedElements, PropagationOptions, ResolvedElements};
+use crate::service::ephemeris_store::EphemerisStore;
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
 use crate::transport::adapter::tle_client::TleGrpcClient;
 
+/// Largest list of satellites whose TLEs are fetched one by one rather than picked
+/// from a catalog listing.
+const PER_SATELLITE_FETCH_LIMIT: usize = 64;
+
 pub struct PositionService {
     tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    ephemeris_store: Arc<EphemerisStore>,
+    age_policy: Arc<TleAgePolicy>,
+    eop: Arc<EopTable>,
 }
 
 impl PositionService {
-    pub const fn new(tle_grpc_client: Arc<TleGrpcClient>) -> Self {
-        Self { tle_grpc_client }
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        ephemeris_store: Arc<EphemerisStore>,
+        age_policy: Arc<TleAgePolicy>,
+        eop: Arc<EopTable>,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            ephemeris_store,
+            age_policy,
+            eop,
+        }
     }
 
     pub async fn get_position_with_metadata(
         &self,
-        satellite_identifier: SatelliteIdentifier,
+        source: ElementSetSource,
         datetime: DateTime<Utc>,
-        compute: &PositionComputation,
+        compute: PositionComputation,
+        options: PropagationOptions,
     ) -> Result<(SatellitePosition, ComputationMetadata), PropagationError> {
-        let tle = self
-            .tle_grpc_client
-            .get_tle(satellite_identifier.clone())
-            .await?;
+        let elements = match source {
+            ElementSetSource::Ephemeris(id) => {
+                self.ephemeris_store.resolve(&id, options.propagator)?
+            }
+            source => {
+                ResolvedElements::resolve(
+                    &self.tle_grpc_client,
+                    &self.propagator_cache,
+                    source,
+                    datetime,
+                    options.propagator,
+                )
+                .await?
+            }
+        };
+        let warnings = self
+            .age_policy
+            .check(&elements, options.age_limits, datetime, datetime)?;
 
-        let position = Propagator::from_tle(&tle)?.position_at(datetime, compute)?;
+        let propagator = elements.propagator.clone();
+        let eop = self.eop.clone();
+        let position =
+            tokio::task::spawn_blocking(move || propagator.position_at(datetime, &compute, &eop))
+                .await??;
         let metadata = ComputationMetadata {
-            propagation_model: "SGP4".to_string(),
+            propagation: elements.propagator.method(),
             computation_time: datetime,
-            norad_id: tle.norad_id,
-            satellite_name: tle.satellite_name,
-            tle_epoch: tle.epoch,
+            norad_id: elements.norad_id,
+            satellite_name: elements.satellite_name,
+            tle_epoch: elements.epoch,
+            eop_degraded: !self.eop.covers(datetime),
+            inline_element_set: elements.inline,
+            warnings,
         };
 
         Ok((position, metadata))
     }
+
+    /// Propagate many satellites to the same instant.
+    ///
+    /// Up to `PER_SATELLITE_FETCH_LIMIT` listed satellites have their TLEs fetched one
+    /// by one, concurrently and through the TLE cache; larger lists and `None`, which
+    /// selects every satellite in the catalog, are resolved from a single catalog
+    /// listing (TLEs selected relative to `datetime` are still fetched one by one).
+    /// SGP4 then runs in parallel on the blocking thread pool. Only a failure to list
+    /// the catalog fails the whole batch; unknown satellites, invalid inline element
+    /// sets, TLEs past their maximum age and propagation failures are reported per item.
+    pub async fn get_positions_with_metadata(
+        &self,
+        sources: Option<Vec<ElementSetSource>>,
+        datetime: DateTime<Utc>,
+        compute: PositionComputation,
+        options: PropagationOptions,
+    ) -> Result<Vec<(ElementSetSource, PositionOutcome)>, PropagationError> {
+        let items = match sources {
+            Some(sources) if sources.len() <= PER_SATELLITE_FETCH_LIMIT => {
+                self.fetch_each(sources, datetime).await?
+            }
+            Some(sources) => self.fetch_from_catalog(sources, datetime).await?,
+            None => self
+                .tle_grpc_client
+                .list_tles()
+                .await?
+                .into_iter()
+                .map(|tle| {
+                    (
+                        ElementSetSource::Catalog(
+                            SatelliteIdentifier::NoradId(tle.norad_id),
+                            TleSelection::Latest,
+                        ),
+                        Ok(FetchedElements::Catalog(tle)),
+                    )
+                })
+                .collect(),
+        };
+
+        let propagator_cache = self.propagator_cache.clone();
+        let age_policy = self.age_policy.clone();
+        let eop = self.eop.clone();
+        let results = tokio::task::spawn_blocking(move || {
+            items
+                .into_par_iter()
+                .map(|(source, fetched)| {
+                    let outcome = fetched
+                        .and_then(|fetched| fetched.build(&propagator_cache, options.propagator))
+                        .and_then(|elements| {
+                            let warnings = age_policy.check(
+                                &elements,
+                                options.age_limits,
+                                datetime,
+                                datetime,
+                            )?;
+                            propagate(&eop, &elements, datetime, &compute, warnings)
+                        });
+                    (source, outcome)
+                })
+                .collect()
+        })
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn fetch_each(
+        &self,
+        sources: Vec<ElementSetSource>,
+        datetime: DateTime<Utc>,
+    ) -> Result<Vec<FetchedItem>, PropagationError> {
+        let mut tasks = JoinSet::new();
+        for (index, source) in sources.into_iter().enumerate() {
+            let tle_grpc_client = self.tle_grpc_client.clone();
+            tasks.spawn(async move {
+                let fetched =
+                    FetchedElements::fetch(&tle_grpc_client, source.clone(), datetime).await;
+                (index, (source, fetched))
+            });
+        }
+
+        let mut items = Vec::with_capacity(tasks.len());
+        while let Some(item) = tasks.join_next().await {
+            items.push(item?);
+        }
+        items.sort_unstable_by_key(|&(index, _)| index);
+
+        Ok(items.into_iter().map(|(_, item)| item).collect())
+    }
+
+    async fn fetch_from_catalog(
+        &self,
+        sources: Vec<ElementSetSource>,
+        datetime: DateTime<Utc>,
+    ) -> Result<Vec<FetchedItem>, PropagationError> {
+        let catalog = self.tle_grpc_client.list_tles().await?;
+        let by_norad_id: HashMap<u32, &Tle> =
+            catalog.iter().map(|tle| (tle.norad_id, tle)).collect();
+        let by_name: HashMap<&str, &Tle> = catalog
+            .iter()
+            .map(|tle| (tle.satellite_name.as_str(), tle))
+            .collect();
+
+        let mut items = Vec::with_capacity(sources.len());
+        for source in sources {
+            let fetched = match &source {
+                ElementSetSource::Catalog(identifier, TleSelection::Latest) => match identifier {
+                    SatelliteIdentifier::NoradId(id) => by_norad_id.get(id),
+                    SatelliteIdentifier::Name(name) => by_name.get(name.as_str()),
+                }
+                .map(|&tle| FetchedElements::Catalog(tle.clone()))
+                .ok_or_else(|| {
+                    PropagationError::TleFetch(tonic::Status::not_found(format!(
+                        "TLE not found for {identifier}"
+                    )))
+                }),
+                // older epochs are not part of the catalog listing, and ephemerides are
+                // only served one at a time
+                ElementSetSource::Catalog(..) | ElementSetSource::Ephemeris(_) => {
+                    FetchedElements::fetch(&self.tle_grpc_client, source.clone(), datetime).await
+                }
+                ElementSetSource::Inline(elements) => Ok(FetchedElements::Inline(elements.clone())),
+            };
+            items.push((source, fetched));
+        }
+
+        Ok(items)
+    }
+}
+
+type FetchedItem = (ElementSetSource, Result<FetchedElements, PropagationError>);
+
+pub type PositionOutcome = Result<(SatellitePosition, ComputationMetadata), PropagationError>;
+
+fn propagate(
+    eop: &EopTable,
+    elements: &ResolvedElements,
+    datetime: DateTime<Utc>,
+    compute: &PositionComputation,
+    warnings: Vec<String>,
+) -> PositionOutcome {
+    let position = elements.propagator.position_at(datetime, compute, eop)?;
+    let metadata = ComputationMetadata {
+        propagation: elements.propagator.method(),
+        computation_time: datetime,
+        norad_id: elements.norad_id,
+        satellite_name: elements.satellite_name.clone(),
+        tle_epoch: elements.epoch,
+        eop_degraded: !eop.covers(datetime),
+        inline_element_set: elements.inline,
+        warnings,
+    };
+
+    Ok((position, metadata))
 }
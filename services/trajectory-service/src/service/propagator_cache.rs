@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use prometheus::{IntCounter, IntGauge, Registry};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::astro::models::{PropagatorKind, Tle};
+use crate::astro::propagator::Propagator;
+use crate::domain::errors::PropagationError;
+
+struct CachedPropagator {
+    epoch: DateTime<Utc>,
+    propagator: Arc<Propagator>,
+    last_used: Instant,
+}
+
+#[derive(Serialize)]
+pub struct PropagatorCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub max_entries: usize,
+}
+
+/// Shared propagators keyed by NORAD ID, propagator model and TLE epoch.
+///
+/// Only the newest epoch of every satellite and model is kept: a newer epoch replaces
+/// the cached propagator, while an older one (e.g. for historical requests) is built
+/// without being cached. When the size bound is hit the least recently used entry is
+/// evicted.
+pub struct PropagatorCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<(u32, PropagatorKind), CachedPropagator>>,
+    hits: IntCounter,
+    misses: IntCounter,
+    evictions: IntCounter,
+    size: IntGauge,
+}
+
+impl PropagatorCache {
+    pub fn new(max_entries: usize) -> prometheus::Result<Self> {
+        Ok(Self {
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+            hits: IntCounter::new("propagator_cache_hits_total", "Propagator cache hits")?,
+            misses: IntCounter::new("propagator_cache_misses_total", "Propagator cache misses")?,
+            evictions: IntCounter::new(
+                "propagator_cache_evictions_total",
+                "Propagators evicted to respect the size bound",
+            )?,
+            size: IntGauge::new(
+                "propagator_cache_entries",
+                "Propagators currently held in the cache",
+            )?,
+        })
+    }
+
+    /// Expose the cache metrics through a Prometheus registry.
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.hits.clone()))?;
+        registry.register(Box::new(self.misses.clone()))?;
+        registry.register(Box::new(self.evictions.clone()))?;
+        registry.register(Box::new(self.size.clone()))?;
+        Ok(())
+    }
+
+    pub fn stats(&self) -> PropagatorCacheStats {
+        PropagatorCacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            evictions: self.evictions.get(),
+            entries: self.lock_entries().len(),
+            max_entries: self.max_entries,
+        }
+    }
+
+    pub fn get_or_build(
+        &self,
+        tle: &Tle,
+        kind: PropagatorKind,
+    ) -> Result<Arc<Propagator>, PropagationError> {
+        let key = (tle.norad_id, kind);
+
+        if let Some(entry) = self.lock_entries().get_mut(&key)
+            && entry.epoch == tle.epoch
+        {
+            self.hits.inc();
+            entry.last_used = Instant::now();
+            return Ok(entry.propagator.clone());
+        }
+
+        self.misses.inc();
+
+        // built outside the lock, constants initialisation is the expensive part
+        let propagator = Arc::new(Propagator::from_tle(tle, kind)?);
+
+        let mut entries = self.lock_entries();
+
+        if entries
+            .get(&key)
+            .is_none_or(|entry| entry.epoch <= tle.epoch)
+        {
+            entries.insert(
+                key,
+                CachedPropagator {
+                    epoch: tle.epoch,
+                    propagator: propagator.clone(),
+                    last_used: Instant::now(),
+                },
+            );
+        }
+
+        while entries.len() > self.max_entries {
+            let Some(least_recent) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(&key, _)| key)
+            else {
+                break;
+            };
+            entries.remove(&least_recent);
+            self.evictions.inc();
+        }
+
+        self.size
+            .set(i64::try_from(entries.len()).unwrap_or(i64::MAX));
+        drop(entries);
+
+        Ok(propagator)
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, HashMap<(u32, PropagatorKind), CachedPropagator>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::astro::models::OrbitRegime;
+use crate::domain::errors::PropagationError;
+use crate::service::element_sets::ResolvedElements;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TleAgeLimits {
+    pub warning: TimeDelta,
+    pub max: TimeDelta,
+}
+
+impl TleAgeLimits {
+    /// Limits from a configuration in days; values too large for a `TimeDelta` disable
+    /// the limit.
+    pub fn from_days(warning: f64, max: f64) -> Self {
+        Self {
+            warning: days(warning),
+            max: days(max),
+        }
+    }
+}
+
+/// Limits supplied with a request; unset ones fall back to the configured regime limits.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TleAgeOverrides {
+    pub warning: Option<TimeDelta>,
+    pub max: Option<TimeDelta>,
+}
+
+/// How far from its epoch a TLE may be propagated, per orbit regime.
+pub struct TleAgePolicy {
+    pub leo: TleAgeLimits,
+    pub meo: TleAgeLimits,
+    pub geo: TleAgeLimits,
+    pub heo: TleAgeLimits,
+}
+
+impl TleAgePolicy {
+    pub const fn limits(&self, regime: OrbitRegime) -> TleAgeLimits {
+        match regime {
+            OrbitRegime::Leo => self.leo,
+            OrbitRegime::Meo => self.meo,
+            OrbitRegime::Geo => self.geo,
+            OrbitRegime::Heo => self.heo,
+        }
+    }
+
+    /// Check propagating `elements` to every time between `start` and `end`.
+    ///
+    /// Fails when `start` is past the maximum age and returns the warnings for the
+    /// metadata otherwise; the rest of a window past the maximum age only warns, so
+    /// that long windows stay usable with fresh TLEs. Ephemerides have no epoch to age
+    /// from and always pass.
+    pub fn check(
+        &self,
+        elements: &ResolvedElements,
+        overrides: TleAgeOverrides,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<String>, PropagationError> {
+        let (Some(epoch), Some(regime)) = (elements.epoch, elements.propagator.orbit_regime())
+        else {
+            return Ok(Vec::new());
+        };
+        let limits = self.limits(regime);
+        let warning = overrides.warning.unwrap_or(limits.warning);
+        let max = overrides.max.unwrap_or(limits.max);
+
+        let start_age = (start - epoch).abs();
+        let age = start_age.max((end - epoch).abs());
+
+        if start_age > max {
+            return Err(PropagationError::TleTooOld {
+                epoch,
+                age_days: fractional_days(start_age),
+                max_days: fractional_days(max),
+                regime,
+            });
+        }
+
+        let mut warnings = Vec::new();
+        if age > max {
+            warnings.push(format!(
+                "TLE epoch {epoch} is {:.1} days from the end of the requested window, past \
+                 the {:.1} day limit for {regime}; results late in the window are unreliable",
+                fractional_days(age),
+                fractional_days(max),
+            ));
+        } else if age > warning {
+            warnings.push(format!(
+                "TLE epoch {epoch} is {:.1} days from the requested time, past the {:.1} day \
+                 warning age for {regime}; accuracy is degraded",
+                fractional_days(age),
+                fractional_days(warning),
+            ));
+        }
+
+        Ok(warnings)
+    }
+}
+
+fn days(days: f64) -> TimeDelta {
+    std::time::Duration::try_from_secs_f64(days * 86_400.0)
+        .ok()
+        .and_then(|duration| TimeDelta::from_std(duration).ok())
+        .unwrap_or(TimeDelta::MAX)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fractional_days(delta: TimeDelta) -> f64 {
+    delta.num_seconds() as f64 / 86_400.0
+}
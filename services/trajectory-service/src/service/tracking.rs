@@ -0,0 +1,223 @@
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::eop::EopTable;
+use crate::astro::look_angles::LookAnglesComputation;
+use crate::astro::models::{PropagatorKind, TrackingSample};
+use crate::astro::position::PositionComputation;
+use crate::astro::propagator::Propagator;
+use crate::domain::errors::PropagationError;
+use crate::domain::models::{ComputationMetadata, ElementSetSource, SatelliteIdentifier};
+use crate::service::element_sets::{FetchedElements, PropagationOptions, ResolvedElements};
+use crate::service::propagator_cache::PropagatorCache;
+use crate::service::tle_age::TleAgePolicy;
+use crate::transport::adapter::tle_client::TleGrpcClient;
+
+/// Samples buffered per stream before the producer waits for a slow client.
+const CHANNEL_CAPACITY: usize = 16;
+
+pub type TrackingItem = Result<(TrackingSample, ComputationMetadata), PropagationError>;
+
+pub struct TrackingService {
+    tle_grpc_client: Arc<TleGrpcClient>,
+    propagator_cache: Arc<PropagatorCache>,
+    age_policy: Arc<TleAgePolicy>,
+    eop: Arc<EopTable>,
+    tle_refresh_interval: Duration,
+}
+
+impl TrackingService {
+    pub const fn new(
+        tle_grpc_client: Arc<TleGrpcClient>,
+        propagator_cache: Arc<PropagatorCache>,
+        age_policy: Arc<TleAgePolicy>,
+        eop: Arc<EopTable>,
+        tle_refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            tle_grpc_client,
+            propagator_cache,
+            age_policy,
+            eop,
+            tle_refresh_interval,
+        }
+    }
+
+    /// Start emitting samples every `interval` of wall-clock time.
+    ///
+    /// The initial TLE is fetched before returning, so an unknown satellite fails the call
+    /// itself rather than the stream. Afterwards a single propagator is reused for the
+    /// lifetime of the stream and only rebuilt when a TLE with a newer epoch shows up;
+    /// inline element sets are never refreshed.
+    /// The stream ends with an error once the TLE gets past its maximum age.
+    /// The producer stops as soon as the receiver is dropped.
+    pub async fn track(
+        &self,
+        source: ElementSetSource,
+        observer: Option<Geodetic>,
+        interval: Duration,
+        position_compute: PositionComputation,
+        look_angles_compute: LookAnglesComputation,
+        options: PropagationOptions,
+    ) -> Result<mpsc::Receiver<TrackingItem>, PropagationError> {
+        let mut elements = ResolvedElements::resolve(
+            &self.tle_grpc_client,
+            &self.propagator_cache,
+            source.clone(),
+            Utc::now(),
+            options.propagator,
+        )
+        .await?;
+        let now = Utc::now();
+        self.age_policy
+            .check(&elements, options.age_limits, now, now)?;
+
+        let tle_grpc_client = self.tle_grpc_client.clone();
+        let propagator_cache = self.propagator_cache.clone();
+        let age_policy = self.age_policy.clone();
+        let eop = self.eop.clone();
+        let compute = Arc::new((observer, position_compute, look_angles_compute));
+        let tle_refresh_interval = self.tle_refresh_interval;
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            let mut last_refresh = Instant::now();
+
+            loop {
+                tokio::select! {
+                    () = tx.closed() => break,
+                    _ = ticker.tick() => {}
+                }
+
+                if let ElementSetSource::Catalog(identifier, _) = &source
+                    && last_refresh.elapsed() >= tle_refresh_interval
+                {
+                    last_refresh = Instant::now();
+
+                    if let Some(latest) = refresh_tle(
+                        &tle_grpc_client,
+                        &propagator_cache,
+                        identifier,
+                        &elements,
+                        options.propagator,
+                    )
+                    .await
+                    {
+                        elements = latest;
+                    }
+                }
+
+                let (propagator, sample_eop, compute) =
+                    (elements.propagator.clone(), eop.clone(), compute.clone());
+                let sampled = tokio::task::spawn_blocking(move || {
+                    let (observer, position_compute, look_angles_compute) = &*compute;
+                    sample(
+                        &propagator,
+                        &sample_eop,
+                        observer.as_ref(),
+                        position_compute,
+                        look_angles_compute,
+                    )
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.into()));
+
+                let item = sampled.and_then(|sample| {
+                    let warnings = age_policy.check(
+                        &elements,
+                        options.age_limits,
+                        sample.datetime,
+                        sample.datetime,
+                    )?;
+                    let metadata = ComputationMetadata {
+                        propagation: elements.propagator.method(),
+                        computation_time: sample.datetime,
+                        norad_id: elements.norad_id,
+                        satellite_name: elements.satellite_name.clone(),
+                        tle_epoch: elements.epoch,
+                        eop_degraded: !eop.covers(sample.datetime),
+                        inline_element_set: elements.inline,
+                        warnings,
+                    };
+                    Ok((sample, metadata))
+                });
+
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+fn sample(
+    propagator: &Propagator,
+    eop: &EopTable,
+    observer: Option<&Geodetic>,
+    position_compute: &PositionComputation,
+    look_angles_compute: &LookAnglesComputation,
+) -> Result<TrackingSample, PropagationError> {
+    let datetime = Utc::now();
+
+    let position = propagator.position_at(datetime, position_compute, eop)?;
+    let look_angles = observer
+        .map(|observer| propagator.look_angles_at(datetime, observer, look_angles_compute, eop))
+        .transpose()?;
+
+    Ok(TrackingSample {
+        datetime,
+        position,
+        look_angles,
+    })
+}
+
+/// Fetch the current TLE, bypassing the TLE cache, and rebuild the propagator if its
+/// epoch is newer.
+///
+/// Failures are logged and leave the stream on the TLE it already has.
+async fn refresh_tle(
+    tle_grpc_client: &TleGrpcClient,
+    propagator_cache: &PropagatorCache,
+    satellite_identifier: &SatelliteIdentifier,
+    current: &ResolvedElements,
+    kind: PropagatorKind,
+) -> Option<ResolvedElements> {
+    let latest = match tle_grpc_client
+        .fetch_latest_tle(satellite_identifier.clone())
+        .await
+    {
+        Ok(latest) if Some(latest.epoch) > current.epoch => latest,
+        Ok(_) => return None,
+        Err(e) => {
+            tracing::warn!("failed to refresh TLE for {satellite_identifier}: {e}");
+            return None;
+        }
+    };
+
+    let epoch = latest.epoch;
+    match FetchedElements::Catalog(latest).build(propagator_cache, kind) {
+        Ok(latest) => {
+            tracing::info!(
+                "switching {satellite_identifier} to TLE epoch {epoch} (was {})",
+                current
+                    .epoch
+                    .map(|epoch| epoch.to_string())
+                    .unwrap_or_default()
+            );
+            Some(latest)
+        }
+        Err(e) => {
+            tracing::warn!("ignoring unusable TLE for {satellite_identifier}: {e}");
+            None
+        }
+    }
+}
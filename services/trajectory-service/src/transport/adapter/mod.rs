@@ -1 +1,2 @@
+pub mod tle_cache;
 pub mod tle_client;
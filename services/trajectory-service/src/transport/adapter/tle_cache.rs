@@ -0,0 +1,280 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::astro::models::Tle;
+use crate::domain::models::SatelliteIdentifier;
+
+pub struct TleCacheConfig {
+    pub ttl: Duration,
+    pub max_entries: usize,
+}
+
+/// Cache lookup key; satellite names are kept exactly as given, as the TLE service
+/// matches them exactly and a hit must not find what a miss would not.
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub enum CacheKey {
+    NoradId(u32),
+    Name(String),
+}
+
+impl CacheKey {
+    fn name(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<&SatelliteIdentifier> for CacheKey {
+    fn from(identifier: &SatelliteIdentifier) -> Self {
+        match identifier {
+            SatelliteIdentifier::NoradId(id) => Self::NoradId(*id),
+            SatelliteIdentifier::Name(name) => Self::name(name),
+        }
+    }
+}
+
+struct CacheEntry {
+    tle: Tle,
+    inserted_at: Instant,
+    /// Position in the insertion order, the key of the entry in [`Entries::order`].
+    sequence: u64,
+}
+
+/// Cached entries with an index of their insertion order, so the oldest one is found
+/// without scanning them all.
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<CacheKey, CacheEntry>,
+    order: BTreeMap<u64, CacheKey>,
+    next_sequence: u64,
+}
+
+impl Entries {
+    fn len(&self) -> usize {
+        self.by_key.len()
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<&CacheEntry> {
+        self.by_key.get(key)
+    }
+
+    /// Insert or replace the entry of `key`, as the most recent insertion.
+    fn insert(&mut self, key: CacheKey, tle: Tle) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        self.order.insert(sequence, key.clone());
+        let entry = CacheEntry {
+            tle,
+            inserted_at: Instant::now(),
+            sequence,
+        };
+        if let Some(replaced) = self.by_key.insert(key, entry) {
+            self.order.remove(&replaced.sequence);
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(removed) = self.by_key.remove(key) {
+            self.order.remove(&removed.sequence);
+        }
+    }
+
+    fn remove_oldest(&mut self) -> bool {
+        let Some((_, key)) = self.order.pop_first() else {
+            return false;
+        };
+        self.by_key.remove(&key);
+        true
+    }
+}
+
+#[derive(Serialize)]
+pub struct TleCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub ttl_secs: u64,
+    pub max_entries: usize,
+}
+
+/// In-memory TLE cache used by [`TleGrpcClient`](super::tle_client::TleGrpcClient).
+///
+/// Entries expire after a fixed TTL, the number of entries is bounded (oldest insertions
+/// are evicted first) and an entry is never replaced by a TLE with an older epoch.
+/// Concurrent misses for the same key are collapsed into a single upstream request.
+pub struct TleCache {
+    config: TleCacheConfig,
+    entries: Mutex<Entries>,
+    in_flight: Mutex<HashMap<CacheKey, Arc<tokio::sync::Mutex<()>>>>,
+    hits: IntCounter,
+    misses: IntCounter,
+    evictions: IntCounter,
+    size: IntGauge,
+}
+
+impl TleCache {
+    pub fn new(config: TleCacheConfig) -> prometheus::Result<Self> {
+        Ok(Self {
+            config,
+            entries: Mutex::new(Entries::default()),
+            in_flight: Mutex::new(HashMap::new()),
+            hits: IntCounter::new("tle_cache_hits_total", "TLE cache hits")?,
+            misses: IntCounter::new("tle_cache_misses_total", "TLE cache misses")?,
+            evictions: IntCounter::new(
+                "tle_cache_evictions_total",
+                "TLE cache entries evicted to respect the size bound",
+            )?,
+            size: IntGauge::new(
+                "tle_cache_entries",
+                "Entries currently held in the TLE cache",
+            )?,
+        })
+    }
+
+    /// Expose the cache metrics through a Prometheus registry.
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.hits.clone()))?;
+        registry.register(Box::new(self.misses.clone()))?;
+        registry.register(Box::new(self.evictions.clone()))?;
+        registry.register(Box::new(self.size.clone()))?;
+        Ok(())
+    }
+
+    pub fn stats(&self) -> TleCacheStats {
+        TleCacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            evictions: self.evictions.get(),
+            entries: self.lock_entries().len(),
+            ttl_secs: self.config.ttl.as_secs(),
+            max_entries: self.config.max_entries,
+        }
+    }
+
+    /// Look up a fresh entry, counting a hit when one is found.
+    pub fn get(&self, key: &CacheKey) -> Option<Tle> {
+        let mut entries = self.lock_entries();
+
+        match entries.get(key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.config.ttl => {
+                self.hits.inc();
+                Some(entry.tle.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                self.size.set(gauge_value(entries.len()));
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn record_miss(&self) {
+        self.misses.inc();
+    }
+
+    /// Store a freshly fetched TLE under the requested key as well as under its
+    /// NORAD ID and name, so either kind of identifier hits afterwards.
+    pub fn insert(&self, requested: &CacheKey, tle: &Tle) {
+        let mut entries = self.lock_entries();
+
+        for key in [
+            requested.clone(),
+            CacheKey::NoradId(tle.norad_id),
+            CacheKey::name(&tle.satellite_name),
+        ] {
+            let stale = entries.get(&key).is_none_or(|entry| {
+                entry.tle.epoch <= tle.epoch || entry.inserted_at.elapsed() >= self.config.ttl
+            });
+
+            if stale {
+                entries.insert(key, tle.clone());
+            }
+        }
+
+        while entries.len() > self.config.max_entries && entries.remove_oldest() {
+            self.evictions.inc();
+        }
+
+        self.size.set(gauge_value(entries.len()));
+    }
+
+    /// Replace cached entries whose epoch is older than a TLE seen elsewhere
+    /// (e.g. in a catalog listing). Satellites that are not cached are left out.
+    pub fn refresh(&self, tle: &Tle) {
+        let mut entries = self.lock_entries();
+
+        for key in [
+            CacheKey::NoradId(tle.norad_id),
+            CacheKey::name(&tle.satellite_name),
+        ] {
+            if entries.get(&key).is_some_and(|entry| {
+                entry.tle.norad_id == tle.norad_id && entry.tle.epoch < tle.epoch
+            }) {
+                entries.insert(key, tle.clone());
+            }
+        }
+    }
+
+    /// Lock shared by all concurrent lookups of `key`, used to let only one of them
+    /// go upstream on a miss. It is forgotten once the last lookup holding it is
+    /// dropped, whether it completed or was cancelled.
+    pub fn flight(&self, key: &CacheKey) -> Flight<'_> {
+        let lock = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        Flight {
+            cache: self,
+            key: key.clone(),
+            lock,
+        }
+    }
+
+    fn lock_entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Share of a lookup in the in-flight lock of its key, see [`TleCache::flight`].
+pub struct Flight<'a> {
+    cache: &'a TleCache,
+    key: CacheKey,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Flight<'_> {
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self
+            .cache
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // the map holds the other reference when no other lookup shares the lock
+        if in_flight
+            .get(&self.key)
+            .is_some_and(|lock| Arc::ptr_eq(lock, &self.lock) && Arc::strong_count(lock) == 2)
+        {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+fn gauge_value(len: usize) -> i64 {
+    i64::try_from(len).unwrap_or(i64::MAX)
+}
@@ -1,12 +1,17 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
 use tonic::transport::Channel;
 
 use crate::astro::models::Tle;
-use crate::domain::models::SatelliteIdentifier;
-use crate::transport::adapter::tle_client::tle_grpc::GetTleRequest;
-use crate::transport::grpc::converters::ToChrono;
+use crate::domain::models::{SatelliteIdentifier, TleSelection};
+use crate::transport::adapter::tle_cache::{CacheKey, TleCache};
+use crate::transport::adapter::tle_client::tle_grpc::{GetTleRequest, ListTlesRequest};
+use crate::transport::grpc::converters::{ToChrono, ToProtoTimestamp};
 
 use tle_grpc::tle_service_client::TleServiceClient;
 
+#[allow(clippy::all, clippy::pedantic, clippy::nursery)]
 pub mod tle_grpc {
     tonic::include_proto!("tle");
 }
@@ -14,36 +19,150 @@ pub mod tle_grpc {
 #[derive(Clone)]
 pub struct TleGrpcClient {
     inner: TleServiceClient<Channel>,
+    cache: Arc<TleCache>,
 }
 
 impl TleGrpcClient {
-    pub async fn new(endpoint: String) -> Result<Self, tonic::transport::Error> {
+    pub async fn new(
+        endpoint: String,
+        cache: Arc<TleCache>,
+    ) -> Result<Self, tonic::transport::Error> {
         let client = TleServiceClient::connect(endpoint).await?;
-        Ok(Self { inner: client })
+        Ok(Self {
+            inner: client,
+            cache,
+        })
     }
 
+    /// Get the current TLE of a satellite, served from the cache when possible.
     pub async fn get_tle(
         &self,
         satellite_identifier: SatelliteIdentifier,
     ) -> Result<Tle, tonic::Status> {
-        let mut client = self.inner.clone();
+        let key = CacheKey::from(&satellite_identifier);
+
+        if let Some(tle) = self.cache.get(&key) {
+            return Ok(tle);
+        }
+
+        let flight = self.cache.flight(&key);
+        let _guard = flight.lock().await;
+
+        // another request may have filled the cache while we were waiting
+        if let Some(tle) = self.cache.get(&key) {
+            return Ok(tle);
+        }
+
+        self.cache.record_miss();
+        let tle = self.fetch_tle(satellite_identifier).await?;
+        self.cache.insert(&key, &tle);
+
+        Ok(tle)
+    }
+
+    /// Get the current TLE of a satellite from upstream, bypassing the cache lookup, for
+    /// callers polling for new epochs more often than the cache expires. The cache is
+    /// updated with the result.
+    pub async fn fetch_latest_tle(
+        &self,
+        satellite_identifier: SatelliteIdentifier,
+    ) -> Result<Tle, tonic::Status> {
+        let key = CacheKey::from(&satellite_identifier);
+        let tle = self.fetch_tle(satellite_identifier).await?;
+        self.cache.insert(&key, &tle);
+        Ok(tle)
+    }
+
+    /// Get the TLE of a satellite picked among its stored epochs relative to `at`.
+    ///
+    /// Only the latest TLE is cached, other selections always go upstream.
+    pub async fn get_tle_at(
+        &self,
+        satellite_identifier: SatelliteIdentifier,
+        at: DateTime<Utc>,
+        selection: TleSelection,
+    ) -> Result<Tle, tonic::Status> {
+        let selection = match selection {
+            TleSelection::Latest => return self.get_tle(satellite_identifier).await,
+            TleSelection::Nearest => tle_grpc::TleSelection::Nearest,
+            TleSelection::LatestBefore => tle_grpc::TleSelection::LatestBefore,
+        };
 
         let request = GetTleRequest {
             identifier: Some(satellite_identifier.clone().into()),
+            at_time: Some(at.to_proto_timestamp()?),
+            selection: selection.into(),
         };
+        self.request_tle(request, &satellite_identifier).await
+    }
+
+    async fn fetch_tle(
+        &self,
+        satellite_identifier: SatelliteIdentifier,
+    ) -> Result<Tle, tonic::Status> {
+        let request = GetTleRequest {
+            identifier: Some(satellite_identifier.clone().into()),
+            ..Default::default()
+        };
+        self.request_tle(request, &satellite_identifier).await
+    }
+
+    async fn request_tle(
+        &self,
+        request: GetTleRequest,
+        satellite_identifier: &SatelliteIdentifier,
+    ) -> Result<Tle, tonic::Status> {
+        let mut client = self.inner.clone();
+
         let response = client.get_tle(request).await?.into_inner();
 
-        let t = response.tle.ok_or_else(|| {
-            tonic::Status::not_found(format!("TLE not found for {satellite_identifier}"))
-        })?;
+        response
+            .tle
+            .ok_or_else(|| {
+                tonic::Status::not_found(format!("TLE not found for {satellite_identifier}"))
+            })?
+            .try_into()
+    }
+
+    /// Fetch the whole catalog, keeping only the latest epoch of every satellite.
+    pub async fn list_tles(&self) -> Result<Vec<Tle>, tonic::Status> {
+        let mut client = self.inner.clone();
+
+        let response = client.list_tles(ListTlesRequest {}).await?.into_inner();
+
+        let mut latest: HashMap<u32, Tle> = HashMap::with_capacity(response.tles.len());
+        for t in response.tles {
+            let tle = Tle::try_from(t)?;
+            match latest.get(&tle.norad_id) {
+                Some(existing) if existing.epoch >= tle.epoch => {}
+                _ => {
+                    latest.insert(tle.norad_id, tle);
+                }
+            }
+        }
+
+        let mut tles: Vec<Tle> = latest.into_values().collect();
+        tles.sort_unstable_by_key(|tle| tle.norad_id);
+
+        for tle in &tles {
+            self.cache.refresh(tle);
+        }
+
+        Ok(tles)
+    }
+}
+
+impl TryFrom<tle_grpc::Tle> for Tle {
+    type Error = tonic::Status;
 
+    fn try_from(t: tle_grpc::Tle) -> Result<Self, Self::Error> {
         let epoch = t
             .epoch
             .as_ref()
             .ok_or_else(|| tonic::Status::invalid_argument("Missing epoch"))?
             .to_chrono()?;
 
-        Ok(Tle {
+        Ok(Self {
             norad_id: t.norad_id,
             satellite_name: t.satellite_name,
             line1: t.line1,
@@ -1,23 +1,49 @@
-use chrono::{DateTime, TimeZone, Utc};
-use prost_types::{FieldMask, Timestamp};
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use prost_types::{Duration, FieldMask, Timestamp};
 use tonic::Status;
+use tonic_types::StatusExt;
 use uom::si::angle::{degree, radian};
-use uom::si::f64::{Angle, Length};
+use uom::si::f64::{Angle, Frequency, Length, Pressure, ThermodynamicTemperature, Velocity};
+use uom::si::frequency::hertz;
 use uom::si::length::{kilometer, meter, mile};
+use uom::si::pressure::hectopascal;
+use uom::si::thermodynamic_temperature::degree_celsius;
+use uom::si::velocity::{
+    kilometer_per_hour, kilometer_per_second, meter_per_second, mile_per_hour,
+};
 
-use crate::astro::coords::ecef::Ecef;
-use crate::astro::coords::eci::Eci;
+use crate::astro::celestial::CelestialBody;
+use crate::astro::coords::ecef::{Ecef, EcefVelocity};
+use crate::astro::coords::eci::{Eci, EciVelocity};
+use crate::astro::coords::frames::{EarthFixedFrame, InertialFrame};
 use crate::astro::coords::geodetic::Geodetic;
+use crate::astro::element_set::ElementSet;
+use crate::astro::illumination::Illumination;
+use crate::astro::interpolation::{Ephemeris, EphemerisState, InterpolationMethod};
 use crate::astro::look_angles::LookAnglesComputation;
-use crate::astro::models::{LookAngles, SatellitePosition};
+use crate::astro::models::{
+    CelestialBodyPosition, DerivedElements, Eclipse, LookAngles, MeanElements, OrbitalElements,
+    OsculatingElements, Pass, PassPoint, PropagatorKind, Resonance, SatellitePosition,
+    TrackingSample, VisibleSegment,
+};
+use crate::astro::omm::OmmFormat;
 use crate::astro::position::PositionComputation;
-use crate::domain::errors::TimestampConversionError;
-use crate::domain::models::{ComputationMetadata, SatelliteIdentifier};
+use crate::astro::refraction::Atmosphere;
+use crate::astro::time::{Clock, TimeScale};
+use crate::domain::errors::{PropagationError, TimestampConversionError};
+use crate::domain::models::{
+    ComputationMetadata, ElementSetSource, InlineElementSet, SatelliteIdentifier, TleSelection,
+};
+use crate::service::tle_age::TleAgeOverrides;
 use crate::transport::adapter::tle_client::tle_grpc;
 use crate::transport::grpc::trajectory::trajectory_grpc;
-use crate::transport::grpc::trajectory::trajectory_grpc::unit_settings::{AngleUnit, DistanceUnit};
+use crate::transport::grpc::trajectory::trajectory_grpc::unit_settings;
+use crate::transport::grpc::trajectory::trajectory_grpc::unit_settings::{
+    AngleUnit, DistanceUnit, VelocityUnit,
+};
 use crate::transport::grpc::trajectory::trajectory_grpc::{
-    GeodeticInput, GeodeticOutput, UnitSettings, Vector3, geodetic_input,
+    GeodeticInput, GeodeticOutput, UnitSettings, Vector3, geodetic_input, look_angles_request,
+    predict_passes_request, visibility_filter,
 };
 
 pub trait ToChrono {
@@ -49,6 +75,31 @@ impl ToProtoTimestamp for DateTime<Utc> {
     }
 }
 
+pub trait ToProtoDuration {
+    fn to_proto_duration(&self) -> Duration;
+}
+
+impl ToProtoDuration for TimeDelta {
+    fn to_proto_duration(&self) -> Duration {
+        Duration {
+            seconds: self.num_seconds(),
+            nanos: self.subsec_nanos(),
+        }
+    }
+}
+
+pub trait ToTimeDelta {
+    fn to_time_delta(&self) -> Result<TimeDelta, TimestampConversionError>;
+}
+
+impl ToTimeDelta for Duration {
+    fn to_time_delta(&self) -> Result<TimeDelta, TimestampConversionError> {
+        TimeDelta::try_seconds(self.seconds)
+            .map(|delta| delta + TimeDelta::nanoseconds(i64::from(self.nanos)))
+            .ok_or(TimestampConversionError::InvalidDuration)
+    }
+}
+
 impl TryFrom<GeodeticInput> for Geodetic {
     type Error = Status;
 
@@ -85,24 +136,184 @@ impl TryFrom<GeodeticInput> for Geodetic {
     }
 }
 
+impl TryFrom<trajectory_grpc::Atmosphere> for Atmosphere {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::Atmosphere) -> Result<Self, Self::Error> {
+        let standard = Self::default();
+
+        let pressure = value
+            .pressure_hpa
+            .map_or(standard.pressure, Pressure::new::<hectopascal>);
+
+        let temperature = value.temperature_celsius.map_or(
+            standard.temperature,
+            ThermodynamicTemperature::new::<degree_celsius>,
+        );
+
+        if !pressure.get::<hectopascal>().is_finite() || pressure.get::<hectopascal>() < 0.0 {
+            return Err(Status::invalid_argument("Pressure must not be negative"));
+        }
+
+        if !temperature.get::<degree_celsius>().is_finite()
+            || temperature.get::<degree_celsius>() <= -273.15
+        {
+            return Err(Status::invalid_argument(
+                "Temperature must be above absolute zero",
+            ));
+        }
+
+        Ok(Self {
+            pressure,
+            temperature,
+        })
+    }
+}
+
+/// Check whether a field mask selects `field` itself or any of its subfields.
+///
+/// A plain prefix match is not enough, since e.g. `eci_velocity` starts with `eci`.
+fn mask_has(mask: &FieldMask, field: &str) -> bool {
+    mask.paths.iter().any(|p| {
+        p.strip_prefix(field)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+impl From<predict_passes_request::MinElevation> for Angle {
+    fn from(value: predict_passes_request::MinElevation) -> Self {
+        match value {
+            predict_passes_request::MinElevation::MinElevationDeg(d) => Self::new::<degree>(d),
+            predict_passes_request::MinElevation::MinElevationRad(r) => Self::new::<radian>(r),
+        }
+    }
+}
+
+impl From<look_angles_request::NominalFrequency> for Frequency {
+    fn from(value: look_angles_request::NominalFrequency) -> Self {
+        match value {
+            look_angles_request::NominalFrequency::NominalFrequencyHz(hz) => Self::new::<hertz>(hz),
+            look_angles_request::NominalFrequency::NominalFrequencyMhz(mhz) => {
+                Self::new::<hertz>(mhz * 1e6)
+            }
+        }
+    }
+}
+
+impl From<visibility_filter::MaxSunElevation> for Angle {
+    fn from(value: visibility_filter::MaxSunElevation) -> Self {
+        match value {
+            visibility_filter::MaxSunElevation::MaxSunElevationDeg(d) => Self::new::<degree>(d),
+            visibility_filter::MaxSunElevation::MaxSunElevationRad(r) => Self::new::<radian>(r),
+        }
+    }
+}
+
+impl From<unit_settings::TimeScale> for TimeScale {
+    fn from(value: unit_settings::TimeScale) -> Self {
+        match value {
+            unit_settings::TimeScale::Unspecified | unit_settings::TimeScale::Utc => Self::Utc,
+            unit_settings::TimeScale::Tai => Self::Tai,
+            unit_settings::TimeScale::Tt => Self::Tt,
+            unit_settings::TimeScale::Ut1 => Self::Ut1,
+            unit_settings::TimeScale::Gps => Self::Gps,
+        }
+    }
+}
+
+impl From<trajectory_grpc::EquatorialFrame> for InertialFrame {
+    fn from(value: trajectory_grpc::EquatorialFrame) -> Self {
+        match value {
+            trajectory_grpc::EquatorialFrame::Unspecified
+            | trajectory_grpc::EquatorialFrame::Teme => Self::Teme,
+            trajectory_grpc::EquatorialFrame::J2000 => Self::J2000,
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::Frame> for InertialFrame {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::Frame) -> Result<Self, Self::Error> {
+        match value {
+            trajectory_grpc::Frame::Unspecified | trajectory_grpc::Frame::Teme => Ok(Self::Teme),
+            trajectory_grpc::Frame::J2000 => Ok(Self::J2000),
+            trajectory_grpc::Frame::Gcrs => Ok(Self::Gcrs),
+            trajectory_grpc::Frame::Pef | trajectory_grpc::Frame::Itrf => Err(
+                Status::invalid_argument("frame must be an inertial frame (TEME, J2000 or GCRS)"),
+            ),
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::Frame> for EarthFixedFrame {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::Frame) -> Result<Self, Self::Error> {
+        match value {
+            trajectory_grpc::Frame::Unspecified | trajectory_grpc::Frame::Pef => Ok(Self::Pef),
+            trajectory_grpc::Frame::Itrf => Ok(Self::Itrf),
+            trajectory_grpc::Frame::Teme
+            | trajectory_grpc::Frame::J2000
+            | trajectory_grpc::Frame::Gcrs => Err(Status::invalid_argument(
+                "earth_fixed_frame must be an Earth-fixed frame (PEF or ITRF)",
+            )),
+        }
+    }
+}
+
+impl From<InertialFrame> for trajectory_grpc::Frame {
+    fn from(value: InertialFrame) -> Self {
+        match value {
+            InertialFrame::Teme => Self::Teme,
+            InertialFrame::J2000 => Self::J2000,
+            InertialFrame::Gcrs => Self::Gcrs,
+        }
+    }
+}
+
+impl From<EarthFixedFrame> for trajectory_grpc::Frame {
+    fn from(value: EarthFixedFrame) -> Self {
+        match value {
+            EarthFixedFrame::Pef => Self::Pef,
+            EarthFixedFrame::Itrf => Self::Itrf,
+        }
+    }
+}
+
 impl From<&FieldMask> for PositionComputation {
     fn from(mask: &FieldMask) -> Self {
-        let has = |prefix: &str| mask.paths.iter().any(|p| p.starts_with(prefix));
+        let has = |field: &str| mask_has(mask, field);
         Self {
             eci: has("eci"),
             ecef: has("ecef"),
             geodetic: has("geodetic"),
+            eci_velocity: has("eci_velocity"),
+            ecef_velocity: has("ecef_velocity"),
+            illumination: has("illumination"),
+            inertial_frame: InertialFrame::default(),
+            earth_fixed_frame: EarthFixedFrame::default(),
         }
     }
 }
 
 impl From<&FieldMask> for LookAnglesComputation {
     fn from(mask: &FieldMask) -> Self {
-        let has = |prefix: &str| mask.paths.iter().any(|p| p.starts_with(prefix));
+        let has = |field: &str| mask_has(mask, field);
         Self {
             azimuth: has("azimuth"),
             elevation: has("elevation"),
+            apparent_elevation: has("apparent_elevation"),
             range: has("range"),
+            range_rate: has("range_rate"),
+            downlink_frequency: has("downlink_frequency_hz"),
+            uplink_frequency: has("uplink_frequency_hz"),
+            nominal_frequency: None,
+            atmosphere: None,
+            right_ascension: has("right_ascension"),
+            declination: has("declination"),
+            hour_angle: has("hour_angle"),
+            equatorial_frame: InertialFrame::default(),
         }
     }
 }
@@ -120,32 +331,300 @@ impl From<SatelliteIdentifier> for tle_grpc::SatelliteIdentifier {
     }
 }
 
-impl TryFrom<trajectory_grpc::SatelliteIdentifier> for SatelliteIdentifier {
+impl From<ElementSetSource> for trajectory_grpc::SatelliteIdentifier {
+    fn from(source: ElementSetSource) -> Self {
+        use trajectory_grpc::satellite_identifier::Kind;
+
+        let (kind, selection) = match source {
+            ElementSetSource::Catalog(SatelliteIdentifier::NoradId(id), selection) => {
+                (Kind::NoradId(id), selection)
+            }
+            ElementSetSource::Catalog(SatelliteIdentifier::Name(name), selection) => {
+                (Kind::SatelliteName(name), selection)
+            }
+            ElementSetSource::Inline(elements) => {
+                (Kind::Inline(elements.into()), TleSelection::Latest)
+            }
+            ElementSetSource::Ephemeris(id) => (Kind::EphemerisId(id), TleSelection::Latest),
+        };
+
+        Self {
+            kind: Some(kind),
+            tle_selection: trajectory_grpc::TleSelection::from(selection).into(),
+        }
+    }
+}
+
+impl From<InlineElementSet> for trajectory_grpc::InlineElementSet {
+    fn from(elements: InlineElementSet) -> Self {
+        use trajectory_grpc::inline_element_set::Format;
+
+        let format = match elements {
+            InlineElementSet::Tle {
+                satellite_name,
+                line1,
+                line2,
+            } => Format::Tle(trajectory_grpc::TleLines {
+                satellite_name: satellite_name.unwrap_or_default(),
+                line1,
+                line2,
+            }),
+            InlineElementSet::Omm(OmmFormat::Json, document) => Format::OmmJson(document),
+            InlineElementSet::Omm(OmmFormat::Xml, document) => Format::OmmXml(document),
+            InlineElementSet::Omm(OmmFormat::Kvn, document) => Format::OmmKvn(document),
+        };
+
+        Self {
+            format: Some(format),
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::OmmFormat> for OmmFormat {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::OmmFormat) -> Result<Self, Self::Error> {
+        match value {
+            trajectory_grpc::OmmFormat::Json => Ok(Self::Json),
+            trajectory_grpc::OmmFormat::Xml => Ok(Self::Xml),
+            trajectory_grpc::OmmFormat::Kvn => Ok(Self::Kvn),
+            trajectory_grpc::OmmFormat::Unspecified => {
+                Err(Status::invalid_argument("OMM format is unspecified"))
+            }
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::TleLines> for ElementSet {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::TleLines) -> Result<Self, Self::Error> {
+        let satellite_name = Some(value.satellite_name).filter(|name| !name.trim().is_empty());
+        Ok(Self::from_tle(satellite_name, &value.line1, &value.line2)?)
+    }
+}
+
+impl TryFrom<&ElementSet> for trajectory_grpc::TleLines {
+    type Error = Status;
+
+    fn try_from(element_set: &ElementSet) -> Result<Self, Self::Error> {
+        let (line1, line2) = element_set.to_tle()?;
+        Ok(Self {
+            satellite_name: element_set.object_name.clone().unwrap_or_default(),
+            line1,
+            line2,
+        })
+    }
+}
+
+impl TryFrom<trajectory_grpc::SatelliteIdentifier> for ElementSetSource {
     type Error = Status;
 
     fn try_from(value: trajectory_grpc::SatelliteIdentifier) -> Result<Self, Self::Error> {
+        use trajectory_grpc::satellite_identifier::Kind;
+
+        let selection = trajectory_grpc::TleSelection::try_from(value.tle_selection)
+            .map_err(|_| Status::invalid_argument("Unknown TLE selection"))?
+            .into();
+
         match value.kind {
-            Some(trajectory_grpc::satellite_identifier::Kind::NoradId(id)) => Ok(Self::NoradId(id)),
-            Some(trajectory_grpc::satellite_identifier::Kind::SatelliteName(name)) => {
-                Ok(Self::Name(name))
+            Some(Kind::NoradId(id)) => {
+                Ok(Self::Catalog(SatelliteIdentifier::NoradId(id), selection))
+            }
+            Some(Kind::SatelliteName(name)) => {
+                Ok(Self::Catalog(SatelliteIdentifier::Name(name), selection))
+            }
+            Some(Kind::Inline(elements)) => Ok(Self::Inline(elements.try_into()?)),
+            Some(Kind::EphemerisId(id)) if id.trim().is_empty() => {
+                Err(Status::invalid_argument("Empty ephemeris ID"))
             }
+            Some(Kind::EphemerisId(id)) => Ok(Self::Ephemeris(id.trim().to_string())),
             None => Err(Status::invalid_argument("Missing satellite identifier")),
         }
     }
 }
 
+impl TryFrom<trajectory_grpc::InlineElementSet> for InlineElementSet {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::InlineElementSet) -> Result<Self, Self::Error> {
+        use trajectory_grpc::inline_element_set::Format;
+
+        match value.format {
+            Some(Format::Tle(tle)) => Ok(Self::Tle {
+                satellite_name: Some(tle.satellite_name).filter(|name| !name.trim().is_empty()),
+                line1: tle.line1,
+                line2: tle.line2,
+            }),
+            Some(Format::OmmJson(document)) => Ok(Self::Omm(OmmFormat::Json, document)),
+            Some(Format::OmmXml(document)) => Ok(Self::Omm(OmmFormat::Xml, document)),
+            Some(Format::OmmKvn(document)) => Ok(Self::Omm(OmmFormat::Kvn, document)),
+            None => Err(Status::invalid_argument("Missing inline element set")),
+        }
+    }
+}
+
+impl From<trajectory_grpc::TleSelection> for TleSelection {
+    fn from(value: trajectory_grpc::TleSelection) -> Self {
+        match value {
+            trajectory_grpc::TleSelection::Unspecified | trajectory_grpc::TleSelection::Latest => {
+                Self::Latest
+            }
+            trajectory_grpc::TleSelection::Nearest => Self::Nearest,
+            trajectory_grpc::TleSelection::LatestBefore => Self::LatestBefore,
+        }
+    }
+}
+
+impl From<TleSelection> for trajectory_grpc::TleSelection {
+    fn from(value: TleSelection) -> Self {
+        match value {
+            TleSelection::Latest => Self::Latest,
+            TleSelection::Nearest => Self::Nearest,
+            TleSelection::LatestBefore => Self::LatestBefore,
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::TleAgeLimits> for TleAgeOverrides {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::TleAgeLimits) -> Result<Self, Self::Error> {
+        let age = |duration: Option<Duration>, name: &str| {
+            duration
+                .map(|duration| {
+                    duration
+                        .to_time_delta()
+                        .ok()
+                        .filter(|age| *age >= TimeDelta::zero())
+                        .ok_or_else(|| Status::invalid_argument(format!("Invalid {name}")))
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            warning: age(value.warning_age, "warning age")?,
+            max: age(value.max_age, "maximum age")?,
+        })
+    }
+}
+
+impl From<trajectory_grpc::PropagatorKind> for PropagatorKind {
+    fn from(value: trajectory_grpc::PropagatorKind) -> Self {
+        match value {
+            trajectory_grpc::PropagatorKind::Unspecified
+            | trajectory_grpc::PropagatorKind::Sgp4 => Self::Sgp4,
+            trajectory_grpc::PropagatorKind::TwoBody => Self::TwoBody,
+            trajectory_grpc::PropagatorKind::J2Secular => Self::J2Secular,
+            trajectory_grpc::PropagatorKind::Cowell => Self::Cowell,
+        }
+    }
+}
+
+impl From<trajectory_grpc::InterpolationMethod> for InterpolationMethod {
+    fn from(value: trajectory_grpc::InterpolationMethod) -> Self {
+        match value {
+            trajectory_grpc::InterpolationMethod::Unspecified
+            | trajectory_grpc::InterpolationMethod::Lagrange => Self::Lagrange,
+            trajectory_grpc::InterpolationMethod::Hermite => Self::Hermite,
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::StateVectorTable> for Ephemeris {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::StateVectorTable) -> Result<Self, Self::Error> {
+        let frame = trajectory_grpc::Frame::try_from(value.frame)
+            .map_err(|_| Status::invalid_argument("Unknown frame"))?
+            .try_into()?;
+        let method = InterpolationMethod::from(
+            trajectory_grpc::InterpolationMethod::try_from(value.interpolation)
+                .map_err(|_| Status::invalid_argument("Unknown interpolation method"))?,
+        );
+        let interpolation_degree = match value.interpolation_degree {
+            0 => method.default_degree(),
+            value => usize::try_from(value)
+                .map_err(|_| Status::invalid_argument("Invalid interpolation degree"))?,
+        };
+
+        let states = value
+            .states
+            .into_iter()
+            .map(|state| {
+                let vector = |vector: Option<Vector3>, name: &str| {
+                    vector
+                        .map(|v| [v.x, v.y, v.z])
+                        .ok_or_else(|| Status::invalid_argument(format!("Missing state {name}")))
+                };
+
+                Ok(EphemerisState {
+                    epoch: state
+                        .epoch
+                        .ok_or_else(|| Status::invalid_argument("Missing state epoch"))?
+                        .to_chrono()?,
+                    position: vector(state.position, "position")?,
+                    velocity: vector(state.velocity, "velocity")?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        Ok(Self::new(
+            Some(value.object_name).filter(|name| !name.trim().is_empty()),
+            Some(value.norad_id).filter(|&id| id != 0),
+            frame,
+            method,
+            interpolation_degree,
+            states,
+        )?)
+    }
+}
+
+impl From<Resonance> for trajectory_grpc::DeepSpaceResonance {
+    fn from(value: Resonance) -> Self {
+        match value {
+            Resonance::None => Self::None,
+            Resonance::OneDay => Self::OneDay,
+            Resonance::HalfDay => Self::HalfDay,
+        }
+    }
+}
+
 impl trajectory_grpc::ComputationMetadata {
     pub fn with_units(
         metadata: ComputationMetadata,
         units: Option<UnitSettings>,
+        clock: &Clock<'_>,
     ) -> Result<Option<Self>, Status> {
+        let propagation_offset = metadata
+            .propagation_offset()
+            .map(|offset| offset.to_proto_duration());
+
         Ok(Some(Self {
-            propagation_model: metadata.propagation_model,
-            computation_time: Some(metadata.computation_time.to_proto_timestamp()?),
+            propagation_model: metadata.propagation.model.to_string(),
+            computation_time: Some(timestamp_in(metadata.computation_time, clock)?),
             norad_id: metadata.norad_id,
             satellite_name: metadata.satellite_name,
-            tle_epoch: Some(metadata.tle_epoch.to_proto_timestamp()?),
+            tle_epoch: metadata
+                .tle_epoch
+                .map(|epoch| timestamp_in(epoch, clock))
+                .transpose()?,
             units,
+            eop_degraded: metadata.eop_degraded,
+            inline_element_set: metadata.inline_element_set,
+            propagation_offset,
+            warnings: metadata.warnings,
+            resonance: metadata
+                .propagation
+                .resonance
+                .map_or(trajectory_grpc::DeepSpaceResonance::Unspecified, Into::into)
+                .into(),
+            gravity_model: metadata
+                .propagation
+                .gravity_model
+                .map(|model| model.to_string())
+                .unwrap_or_default(),
+            afspc_compatibility_mode: metadata.propagation.afspc_compatibility_mode,
         }))
     }
 }
@@ -180,6 +659,36 @@ impl HasXYZ for Ecef {
     }
 }
 
+pub trait HasVelocityXYZ {
+    fn x(&self) -> Velocity;
+    fn y(&self) -> Velocity;
+    fn z(&self) -> Velocity;
+}
+
+impl HasVelocityXYZ for EciVelocity {
+    fn x(&self) -> Velocity {
+        self.x
+    }
+    fn y(&self) -> Velocity {
+        self.y
+    }
+    fn z(&self) -> Velocity {
+        self.z
+    }
+}
+
+impl HasVelocityXYZ for EcefVelocity {
+    fn x(&self) -> Velocity {
+        self.x
+    }
+    fn y(&self) -> Velocity {
+        self.y
+    }
+    fn z(&self) -> Velocity {
+        self.z
+    }
+}
+
 impl Vector3 {
     pub fn from_xyz<T: HasXYZ>(
         coords: Option<&T>,
@@ -223,6 +732,54 @@ impl Vector3 {
     }
 }
 
+impl Vector3 {
+    pub fn from_velocity<T: HasVelocityXYZ>(
+        velocity: Option<&T>,
+        units: Option<UnitSettings>,
+    ) -> Result<Option<Self>, Status> {
+        let Some(velocity) = velocity else {
+            return Ok(None);
+        };
+
+        let velocity_unit = units
+            .as_ref()
+            .and_then(|u| VelocityUnit::try_from(u.velocity_unit).ok())
+            .unwrap_or(VelocityUnit::Unspecified);
+
+        if velocity_unit == VelocityUnit::Unspecified {
+            return Err(Status::invalid_argument(
+                "Velocity unit is unspecified in UnitSettings",
+            ));
+        }
+
+        let (x, y, z) = match velocity_unit {
+            VelocityUnit::MetersPerSecond => (
+                velocity.x().get::<meter_per_second>(),
+                velocity.y().get::<meter_per_second>(),
+                velocity.z().get::<meter_per_second>(),
+            ),
+            VelocityUnit::KilometersPerSecond => (
+                velocity.x().get::<kilometer_per_second>(),
+                velocity.y().get::<kilometer_per_second>(),
+                velocity.z().get::<kilometer_per_second>(),
+            ),
+            VelocityUnit::KilometersPerHour => (
+                velocity.x().get::<kilometer_per_hour>(),
+                velocity.y().get::<kilometer_per_hour>(),
+                velocity.z().get::<kilometer_per_hour>(),
+            ),
+            VelocityUnit::MilesPerHour => (
+                velocity.x().get::<mile_per_hour>(),
+                velocity.y().get::<mile_per_hour>(),
+                velocity.z().get::<mile_per_hour>(),
+            ),
+            VelocityUnit::Unspecified => unreachable!(),
+        };
+
+        Ok(Some(Self { x, y, z }))
+    }
+}
+
 impl GeodeticOutput {
     pub fn from_geodetic(
         geodetic: Option<&Geodetic>,
@@ -269,17 +826,49 @@ impl GeodeticOutput {
     }
 }
 
+impl From<Illumination> for trajectory_grpc::Illumination {
+    fn from(illumination: Illumination) -> Self {
+        match illumination {
+            Illumination::Sunlit => Self::Sunlit,
+            Illumination::Penumbra => Self::Penumbra,
+            Illumination::Umbra => Self::Umbra,
+        }
+    }
+}
+
+impl TryFrom<trajectory_grpc::CelestialBody> for CelestialBody {
+    type Error = Status;
+
+    fn try_from(value: trajectory_grpc::CelestialBody) -> Result<Self, Self::Error> {
+        match value {
+            trajectory_grpc::CelestialBody::Sun => Ok(Self::Sun),
+            trajectory_grpc::CelestialBody::Moon => Ok(Self::Moon),
+            trajectory_grpc::CelestialBody::Unspecified => {
+                Err(Status::invalid_argument("Missing celestial body"))
+            }
+        }
+    }
+}
+
 impl trajectory_grpc::PositionResponse {
     pub fn from_position(
         position: &SatellitePosition,
         metadata: ComputationMetadata,
         units: Option<UnitSettings>,
+        clock: &Clock<'_>,
     ) -> Result<Self, Status> {
         Ok(Self {
-            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units)?,
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
             eci: Vector3::from_xyz(position.eci.as_ref(), units)?,
             ecef: Vector3::from_xyz(position.ecef.as_ref(), units)?,
             geodetic: GeodeticOutput::from_geodetic(position.geodetic.as_ref(), units)?,
+            eci_velocity: Vector3::from_velocity(position.eci_velocity.as_ref(), units)?,
+            ecef_velocity: Vector3::from_velocity(position.ecef_velocity.as_ref(), units)?,
+            illumination: position
+                .illumination
+                .map(|i| trajectory_grpc::Illumination::from(i).into()),
+            eci_frame: trajectory_grpc::Frame::from(position.inertial_frame).into(),
+            ecef_frame: trajectory_grpc::Frame::from(position.earth_fixed_frame).into(),
         })
     }
 }
@@ -289,6 +878,7 @@ impl trajectory_grpc::LookAnglesResponse {
         look_angles: &LookAngles,
         metadata: ComputationMetadata,
         units: Option<UnitSettings>,
+        clock: &Clock<'_>,
     ) -> Result<Self, Status> {
         let distance_unit = units
             .as_ref()
@@ -334,11 +924,463 @@ impl trajectory_grpc::LookAnglesResponse {
             (_, DistanceUnit::Unspecified) => unreachable!(),
         };
 
+        let apparent_elevation = look_angles
+            .apparent_elevation
+            .map(|a| angle_in(a, angle_unit));
+        let right_ascension = look_angles.right_ascension.map(|a| angle_in(a, angle_unit));
+        let declination = look_angles.declination.map(|a| angle_in(a, angle_unit));
+        let hour_angle = look_angles.hour_angle.map(|a| angle_in(a, angle_unit));
+
+        let range_rate = look_angles
+            .range_rate
+            .map(|v| required_velocity_unit(units.as_ref()).map(|unit| velocity_in(v, unit)))
+            .transpose()?;
+
         Ok(Self {
-            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units)?,
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
             azimuth,
             elevation,
             range,
+            range_rate,
+            apparent_elevation,
+            downlink_frequency_hz: look_angles.downlink_frequency.map(|f| f.get::<hertz>()),
+            uplink_frequency_hz: look_angles.uplink_frequency.map(|f| f.get::<hertz>()),
+            right_ascension,
+            declination,
+            hour_angle,
+        })
+    }
+}
+
+/// Time scale of the request timestamps, UTC unless set.
+pub fn time_scale(units: Option<&UnitSettings>) -> Result<TimeScale, Status> {
+    let Some(units) = units else {
+        return Ok(TimeScale::Utc);
+    };
+    unit_settings::TimeScale::try_from(units.time_scale)
+        .map(TimeScale::from)
+        .map_err(|_| Status::invalid_argument("Unknown time scale in UnitSettings"))
+}
+
+pub fn omm_format(format: i32) -> Result<OmmFormat, Status> {
+    trajectory_grpc::OmmFormat::try_from(format)
+        .map_err(|_| Status::invalid_argument("Unknown OMM format"))?
+        .try_into()
+}
+
+/// Timestamp of the UTC instant `datetime` as read on `clock`.
+fn timestamp_in(
+    datetime: DateTime<Utc>,
+    clock: &Clock<'_>,
+) -> Result<Timestamp, TimestampConversionError> {
+    clock.reading(datetime).to_proto_timestamp()
+}
+
+fn required_distance_unit(units: Option<&UnitSettings>) -> Result<DistanceUnit, Status> {
+    match units.and_then(|u| DistanceUnit::try_from(u.distance_unit).ok()) {
+        None | Some(DistanceUnit::Unspecified) => Err(Status::invalid_argument(
+            "Distance unit is unspecified in UnitSettings",
+        )),
+        Some(unit) => Ok(unit),
+    }
+}
+
+fn required_angle_unit(units: Option<&UnitSettings>) -> Result<AngleUnit, Status> {
+    match units.and_then(|u| AngleUnit::try_from(u.angle_unit).ok()) {
+        None | Some(AngleUnit::Unspecified) => Err(Status::invalid_argument(
+            "Angle unit is unspecified in UnitSettings",
+        )),
+        Some(unit) => Ok(unit),
+    }
+}
+
+fn required_velocity_unit(units: Option<&UnitSettings>) -> Result<VelocityUnit, Status> {
+    match units.and_then(|u| VelocityUnit::try_from(u.velocity_unit).ok()) {
+        None | Some(VelocityUnit::Unspecified) => Err(Status::invalid_argument(
+            "Velocity unit is unspecified in UnitSettings",
+        )),
+        Some(unit) => Ok(unit),
+    }
+}
+
+fn length_in(length: Length, unit: DistanceUnit) -> f64 {
+    match unit {
+        DistanceUnit::Meters => length.get::<meter>(),
+        DistanceUnit::Kilometers => length.get::<kilometer>(),
+        DistanceUnit::Miles => length.get::<mile>(),
+        DistanceUnit::Unspecified => unreachable!(),
+    }
+}
+
+fn velocity_in(velocity: Velocity, unit: VelocityUnit) -> f64 {
+    match unit {
+        VelocityUnit::MetersPerSecond => velocity.get::<meter_per_second>(),
+        VelocityUnit::KilometersPerSecond => velocity.get::<kilometer_per_second>(),
+        VelocityUnit::KilometersPerHour => velocity.get::<kilometer_per_hour>(),
+        VelocityUnit::MilesPerHour => velocity.get::<mile_per_hour>(),
+        VelocityUnit::Unspecified => unreachable!(),
+    }
+}
+
+fn angle_in(angle: Angle, unit: AngleUnit) -> f64 {
+    match unit {
+        AngleUnit::Degrees => angle.get::<degree>(),
+        AngleUnit::Radians => angle.get::<radian>(),
+        AngleUnit::Unspecified => unreachable!(),
+    }
+}
+
+impl trajectory_grpc::Pass {
+    pub fn from_pass(
+        pass: &Pass,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        let distance_unit = required_distance_unit(units.as_ref())?;
+        let angle_unit = required_angle_unit(units.as_ref())?;
+
+        Ok(Self {
+            rise_time: Some(timestamp_in(pass.rise_time, clock)?),
+            rise_azimuth: angle_in(pass.rise_azimuth, angle_unit),
+            culmination_time: Some(timestamp_in(pass.culmination_time, clock)?),
+            culmination_elevation: angle_in(pass.culmination_elevation, angle_unit),
+            culmination_range: length_in(pass.culmination_range, distance_unit),
+            set_time: Some(timestamp_in(pass.set_time, clock)?),
+            set_azimuth: angle_in(pass.set_azimuth, angle_unit),
+            visible_segments: pass
+                .visible_segments
+                .iter()
+                .map(|segment| {
+                    trajectory_grpc::VisibleSegment::from_segment(segment, angle_unit, clock)
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl trajectory_grpc::PassPoint {
+    fn from_point(
+        point: &PassPoint,
+        angle_unit: AngleUnit,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            time: Some(timestamp_in(point.time, clock)?),
+            azimuth: angle_in(point.azimuth, angle_unit),
+            elevation: angle_in(point.elevation, angle_unit),
+        })
+    }
+}
+
+impl trajectory_grpc::VisibleSegment {
+    fn from_segment(
+        segment: &VisibleSegment,
+        angle_unit: AngleUnit,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            start: Some(trajectory_grpc::PassPoint::from_point(
+                &segment.start,
+                angle_unit,
+                clock,
+            )?),
+            max: Some(trajectory_grpc::PassPoint::from_point(
+                &segment.max,
+                angle_unit,
+                clock,
+            )?),
+            end: Some(trajectory_grpc::PassPoint::from_point(
+                &segment.end,
+                angle_unit,
+                clock,
+            )?),
+        })
+    }
+}
+
+impl trajectory_grpc::PredictPassesResponse {
+    pub fn from_passes(
+        passes: &[Pass],
+        metadata: ComputationMetadata,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
+            passes: passes
+                .iter()
+                .map(|pass| trajectory_grpc::Pass::from_pass(pass, units, clock))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl trajectory_grpc::EphemerisSample {
+    pub fn from_position(
+        datetime: DateTime<Utc>,
+        position: &SatellitePosition,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            datetime: Some(timestamp_in(datetime, clock)?),
+            eci: Vector3::from_xyz(position.eci.as_ref(), units)?,
+            ecef: Vector3::from_xyz(position.ecef.as_ref(), units)?,
+            geodetic: GeodeticOutput::from_geodetic(position.geodetic.as_ref(), units)?,
+            eci_velocity: Vector3::from_velocity(position.eci_velocity.as_ref(), units)?,
+            ecef_velocity: Vector3::from_velocity(position.ecef_velocity.as_ref(), units)?,
+            illumination: position
+                .illumination
+                .map(|i| trajectory_grpc::Illumination::from(i).into()),
+        })
+    }
+}
+
+impl trajectory_grpc::EphemerisResponse {
+    pub fn from_samples(
+        datetimes: &[DateTime<Utc>],
+        positions: &[SatellitePosition],
+        metadata: ComputationMetadata,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
+            samples: datetimes
+                .iter()
+                .zip(positions)
+                .map(|(&datetime, position)| {
+                    trajectory_grpc::EphemerisSample::from_position(
+                        datetime, position, units, clock,
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl trajectory_grpc::TrackResponse {
+    pub fn from_sample(
+        sample: &TrackingSample,
+        metadata: ComputationMetadata,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        let position = &sample.position;
+        let look_angles = sample.look_angles.as_ref();
+
+        let angle = |angle: Option<Angle>| {
+            angle
+                .map(|a| required_angle_unit(units.as_ref()).map(|unit| angle_in(a, unit)))
+                .transpose()
+        };
+        let length = |length: Option<Length>| {
+            length
+                .map(|l| required_distance_unit(units.as_ref()).map(|unit| length_in(l, unit)))
+                .transpose()
+        };
+
+        Ok(Self {
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
+            eci: Vector3::from_xyz(position.eci.as_ref(), units)?,
+            ecef: Vector3::from_xyz(position.ecef.as_ref(), units)?,
+            geodetic: GeodeticOutput::from_geodetic(position.geodetic.as_ref(), units)?,
+            eci_velocity: Vector3::from_velocity(position.eci_velocity.as_ref(), units)?,
+            ecef_velocity: Vector3::from_velocity(position.ecef_velocity.as_ref(), units)?,
+            illumination: position
+                .illumination
+                .map(|i| trajectory_grpc::Illumination::from(i).into()),
+            azimuth: angle(look_angles.and_then(|la| la.azimuth))?,
+            elevation: angle(look_angles.and_then(|la| la.elevation))?,
+            range: length(look_angles.and_then(|la| la.range))?,
+        })
+    }
+}
+
+impl trajectory_grpc::Eclipse {
+    pub fn from_eclipse(eclipse: &Eclipse, clock: &Clock<'_>) -> Result<Self, Status> {
+        Ok(Self {
+            penumbra_entry: Some(timestamp_in(eclipse.penumbra_entry, clock)?),
+            umbra_entry: eclipse
+                .umbra_entry
+                .map(|t| timestamp_in(t, clock))
+                .transpose()?,
+            umbra_exit: eclipse
+                .umbra_exit
+                .map(|t| timestamp_in(t, clock))
+                .transpose()?,
+            penumbra_exit: Some(timestamp_in(eclipse.penumbra_exit, clock)?),
+        })
+    }
+}
+
+impl trajectory_grpc::EclipsesResponse {
+    pub fn from_eclipses(
+        eclipses: &[Eclipse],
+        metadata: ComputationMetadata,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
+            eclipses: eclipses
+                .iter()
+                .map(|eclipse| trajectory_grpc::Eclipse::from_eclipse(eclipse, clock))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl trajectory_grpc::CelestialBodyResponse {
+    pub fn from_position(
+        body: trajectory_grpc::CelestialBody,
+        datetime: DateTime<Utc>,
+        position: &CelestialBodyPosition,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        let look_angles = position.look_angles.as_ref();
+
+        let angle = |angle: Option<Angle>| {
+            angle
+                .map(|a| required_angle_unit(units.as_ref()).map(|unit| angle_in(a, unit)))
+                .transpose()
+        };
+        let length = |length: Option<Length>| {
+            length
+                .map(|l| required_distance_unit(units.as_ref()).map(|unit| length_in(l, unit)))
+                .transpose()
+        };
+
+        Ok(Self {
+            body: body.into(),
+            datetime: Some(timestamp_in(datetime, clock)?),
+            units,
+            eci: Vector3::from_xyz(position.eci.as_ref(), units)?,
+            ecef: Vector3::from_xyz(position.ecef.as_ref(), units)?,
+            azimuth: angle(look_angles.and_then(|la| la.azimuth))?,
+            elevation: angle(look_angles.and_then(|la| la.elevation))?,
+            range: length(look_angles.and_then(|la| la.range))?,
+        })
+    }
+}
+
+impl trajectory_grpc::MeanElements {
+    pub fn from_mean(
+        mean: &MeanElements,
+        angle_unit: AngleUnit,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            epoch: Some(timestamp_in(mean.epoch, clock)?),
+            inclination: angle_in(mean.inclination, angle_unit),
+            right_ascension: angle_in(mean.right_ascension, angle_unit),
+            eccentricity: mean.eccentricity,
+            argument_of_perigee: angle_in(mean.argument_of_perigee, angle_unit),
+            mean_anomaly: angle_in(mean.mean_anomaly, angle_unit),
+            mean_motion: mean.mean_motion,
+            bstar: mean.bstar,
+        })
+    }
+}
+
+impl trajectory_grpc::DerivedElements {
+    pub fn from_derived(derived: &DerivedElements, distance_unit: DistanceUnit) -> Self {
+        Self {
+            semi_major_axis: length_in(derived.semi_major_axis, distance_unit),
+            period: Some(derived.period.to_proto_duration()),
+            apogee_altitude: length_in(derived.apogee_altitude, distance_unit),
+            perigee_altitude: length_in(derived.perigee_altitude, distance_unit),
+        }
+    }
+}
+
+impl trajectory_grpc::OsculatingElements {
+    pub fn from_osculating(
+        osculating: &OsculatingElements,
+        distance_unit: DistanceUnit,
+        angle_unit: AngleUnit,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        Ok(Self {
+            datetime: Some(timestamp_in(osculating.datetime, clock)?),
+            semi_major_axis: length_in(osculating.semi_major_axis, distance_unit),
+            eccentricity: osculating.eccentricity,
+            inclination: angle_in(osculating.inclination, angle_unit),
+            right_ascension: angle_in(osculating.right_ascension, angle_unit),
+            argument_of_perigee: angle_in(osculating.argument_of_perigee, angle_unit),
+            true_anomaly: angle_in(osculating.true_anomaly, angle_unit),
+            mean_anomaly: angle_in(osculating.mean_anomaly, angle_unit),
+        })
+    }
+}
+
+impl trajectory_grpc::OrbitalElementsResponse {
+    pub fn from_elements(
+        elements: &OrbitalElements,
+        metadata: ComputationMetadata,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        let distance_unit = required_distance_unit(units.as_ref())?;
+        let angle_unit = required_angle_unit(units.as_ref())?;
+
+        Ok(Self {
+            metadata: trajectory_grpc::ComputationMetadata::with_units(metadata, units, clock)?,
+            mean: Some(trajectory_grpc::MeanElements::from_mean(
+                &elements.mean,
+                angle_unit,
+                clock,
+            )?),
+            derived: Some(trajectory_grpc::DerivedElements::from_derived(
+                &elements.derived,
+                distance_unit,
+            )),
+            osculating: Some(trajectory_grpc::OsculatingElements::from_osculating(
+                &elements.osculating,
+                distance_unit,
+                angle_unit,
+                clock,
+            )?),
+        })
+    }
+}
+
+impl From<PropagationError> for trajectory_grpc::ItemError {
+    fn from(error: PropagationError) -> Self {
+        let status = Status::from(error);
+        let reason = status
+            .get_details_error_info()
+            .map(|info| info.reason)
+            .unwrap_or_default();
+
+        Self {
+            code: status.code() as i32,
+            message: status.message().to_string(),
+            reason,
+        }
+    }
+}
+
+impl trajectory_grpc::PositionResult {
+    pub fn from_outcome(
+        source: ElementSetSource,
+        outcome: Result<(SatellitePosition, ComputationMetadata), PropagationError>,
+        units: Option<UnitSettings>,
+        clock: &Clock<'_>,
+    ) -> Result<Self, Status> {
+        let result = match outcome {
+            Ok((position, metadata)) => trajectory_grpc::position_result::Result::Position(
+                trajectory_grpc::PositionResponse::from_position(
+                    &position, metadata, units, clock,
+                )?,
+            ),
+            Err(error) => trajectory_grpc::position_result::Result::Error(error.into()),
+        };
+
+        Ok(Self {
+            identifier: Some(source.into()),
+            result: Some(result),
         })
     }
 }
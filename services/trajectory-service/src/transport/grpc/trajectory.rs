@@ -1,39 +1,188 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
 use tonic::{Request, Response, Status};
+use uom::si::angle::degree;
+use uom::si::f64::{Angle, Frequency};
+use uom::si::frequency::hertz;
 
+use crate::astro::celestial;
+use crate::astro::element_set::ElementSet;
+use crate::astro::eop::EopTable;
+use crate::astro::interpolation::Ephemeris;
 use crate::astro::look_angles::LookAnglesComputation;
+use crate::astro::passes::PassCriteria;
 use crate::astro::position::PositionComputation;
+use crate::astro::time::Clock;
+use crate::service::eclipses::EclipsesService;
+use crate::service::element_sets::PropagationOptions;
+use crate::service::ephemeris::EphemerisService;
+use crate::service::ephemeris_store::EphemerisStore;
 use crate::service::look_angles::LookAnglesService;
+use crate::service::orbital_elements::OrbitalElementsService;
+use crate::service::passes::PassesService;
 use crate::service::position::PositionService;
-use crate::transport::grpc::converters::ToChrono;
+use crate::service::tracking::TrackingService;
+use crate::transport::grpc::converters::{
+    ToChrono, ToProtoTimestamp, ToTimeDelta, omm_format, time_scale,
+};
 
 use trajectory_grpc::{
-    LookAnglesRequest, LookAnglesResponse, PositionRequest, PositionResponse,
-    trajectory_service_server::TrajectoryService,
+    CelestialBody, CelestialBodyRequest, CelestialBodyResponse, ConvertOmmToTleRequest,
+    ConvertOmmToTleResponse, ConvertTleToOmmRequest, ConvertTleToOmmResponse, EclipsesRequest,
+    EclipsesResponse, EphemerisRequest, EphemerisResponse, EquatorialFrame, Frame,
+    LookAnglesRequest, LookAnglesResponse, OrbitalElementsRequest, OrbitalElementsResponse,
+    PositionRequest, PositionResponse, PositionResult, PositionsRequest, PositionsResponse,
+    PredictPassesRequest, PredictPassesResponse, PropagatorKind, TleAgeLimits, TleLines,
+    TrackRequest, TrackResponse, UploadEphemerisRequest, UploadEphemerisResponse,
+    ephemeris_request, positions_request, trajectory_service_server::TrajectoryService,
+    upload_ephemeris_request,
 };
 
+#[allow(clippy::all, clippy::pedantic, clippy::nursery)]
 pub mod trajectory_grpc {
     tonic::include_proto!("trajectory");
 }
 
+/// Longest window accepted by `PredictPasses`.
+const MAX_PASS_WINDOW: TimeDelta = TimeDelta::days(31);
+
+/// Sun elevation used as the end of twilight when a visibility filter sets none.
+const DEFAULT_MAX_SUN_ELEVATION_DEG: f64 = -6.0;
+
+/// Longest window accepted by `GetEclipses`.
+const MAX_ECLIPSE_WINDOW: TimeDelta = TimeDelta::days(31);
+
+/// Largest number of samples returned by a single `GetEphemeris` call.
+const MAX_EPHEMERIS_SAMPLES: usize = 100_000;
+
+/// Shortest sampling interval accepted by `TrackSatellite`.
+const MIN_TRACK_INTERVAL: TimeDelta = TimeDelta::milliseconds(100);
+
+type TrackStream = Pin<Box<dyn Stream<Item = Result<TrackResponse, Status>> + Send>>;
+
+#[allow(clippy::struct_field_names)]
 pub struct TrajectoryGrpcServer {
     position_service: PositionService,
     look_angles_service: LookAnglesService,
+    passes_service: PassesService,
+    ephemeris_service: EphemerisService,
+    tracking_service: TrackingService,
+    eclipses_service: EclipsesService,
+    orbital_elements_service: OrbitalElementsService,
+    ephemeris_store: Arc<EphemerisStore>,
+    eop: Arc<EopTable>,
 }
 
 impl TrajectoryGrpcServer {
+    #[allow(clippy::too_many_arguments)]
     pub const fn new(
         position_service: PositionService,
         look_angles_service: LookAnglesService,
+        passes_service: PassesService,
+        ephemeris_service: EphemerisService,
+        tracking_service: TrackingService,
+        eclipses_service: EclipsesService,
+        orbital_elements_service: OrbitalElementsService,
+        ephemeris_store: Arc<EphemerisStore>,
+        eop: Arc<EopTable>,
     ) -> Self {
         Self {
             position_service,
             look_angles_service,
+            passes_service,
+            ephemeris_service,
+            tracking_service,
+            eclipses_service,
+            orbital_elements_service,
+            ephemeris_store,
+            eop,
         }
     }
 }
 
+/// Propagator and TLE age limits shared by every request propagating an element set.
+fn propagation_options(
+    propagator: i32,
+    tle_age_limits: Option<TleAgeLimits>,
+) -> Result<PropagationOptions, Status> {
+    Ok(PropagationOptions {
+        propagator: PropagatorKind::try_from(propagator)
+            .map_err(|_| Status::invalid_argument("Unknown propagator"))?
+            .into(),
+        age_limits: tle_age_limits
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default(),
+    })
+}
+
+/// Expand the requested sampling into an explicit, bounded list of instants.
+fn ephemeris_datetimes(
+    times: ephemeris_request::Times,
+    clock: &Clock<'_>,
+) -> Result<Vec<DateTime<Utc>>, Status> {
+    let datetimes = match times {
+        ephemeris_request::Times::Range(range) => {
+            let start = clock.utc(
+                range
+                    .start
+                    .ok_or_else(|| Status::invalid_argument("Missing start"))?
+                    .to_chrono()?,
+            );
+
+            let stop = clock.utc(
+                range
+                    .stop
+                    .ok_or_else(|| Status::invalid_argument("Missing stop"))?
+                    .to_chrono()?,
+            );
+
+            let interval = range
+                .step
+                .ok_or_else(|| Status::invalid_argument("Missing step"))?
+                .to_time_delta()?;
+
+            if stop < start {
+                return Err(Status::invalid_argument("Stop must not be before start"));
+            }
+
+            if interval <= TimeDelta::zero() {
+                return Err(Status::invalid_argument("Step must be positive"));
+            }
+
+            std::iter::successors(Some(start), |&datetime| {
+                datetime.checked_add_signed(interval)
+            })
+            .take_while(|&datetime| datetime <= stop)
+            .take(MAX_EPHEMERIS_SAMPLES + 1)
+            .collect::<Vec<_>>()
+        }
+        ephemeris_request::Times::Timestamps(list) => list
+            .timestamps
+            .iter()
+            .map(|timestamp| timestamp.to_chrono().map(|reading| clock.utc(reading)))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    if datetimes.is_empty() {
+        return Err(Status::invalid_argument("No timestamps requested"));
+    }
+
+    if datetimes.len() > MAX_EPHEMERIS_SAMPLES {
+        return Err(Status::invalid_argument(format!(
+            "Ephemeris must not exceed {MAX_EPHEMERIS_SAMPLES} samples"
+        )));
+    }
+
+    Ok(datetimes)
+}
+
 #[tonic::async_trait]
 impl TrajectoryService for TrajectoryGrpcServer {
+    type TrackSatelliteStream = TrackStream;
+
     async fn get_position(
         &self,
         request: Request<PositionRequest>,
@@ -45,21 +194,34 @@ impl TrajectoryService for TrajectoryGrpcServer {
             .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
             .try_into()?;
 
-        let datetime = req
-            .datetime
-            .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
-            .to_chrono()?;
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetime = clock.utc(
+            req.datetime
+                .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
+                .to_chrono()?,
+        );
 
         let mask = req.output_mask.as_ref();
 
-        let compute = mask.map_or_else(PositionComputation::default, PositionComputation::from);
+        let compute = PositionComputation {
+            inertial_frame: Frame::try_from(req.frame)
+                .map_err(|_| Status::invalid_argument("Unknown frame"))?
+                .try_into()?,
+            earth_fixed_frame: Frame::try_from(req.earth_fixed_frame)
+                .map_err(|_| Status::invalid_argument("Unknown earth-fixed frame"))?
+                .try_into()?,
+            ..mask.map_or_else(PositionComputation::default, PositionComputation::from)
+        };
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
 
         let (position, metadata) = self
             .position_service
-            .get_position_with_metadata(identifier, datetime, &compute)
+            .get_position_with_metadata(identifier, datetime, compute, options)
             .await?;
 
-        let response = PositionResponse::from_position(&position, metadata, req.units)?;
+        let response = PositionResponse::from_position(&position, metadata, req.units, &clock)?;
         Ok(Response::new(response))
     }
 
@@ -76,10 +238,13 @@ impl TrajectoryService for TrajectoryGrpcServer {
             })?
             .try_into()?;
 
-        let datetime = req
-            .datetime
-            .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
-            .to_chrono()?;
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetime = clock.utc(
+            req.datetime
+                .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
+                .to_chrono()?,
+        );
 
         let observer = req
             .observer
@@ -88,14 +253,471 @@ impl TrajectoryService for TrajectoryGrpcServer {
 
         let mask = req.output_mask.as_ref();
 
-        let compute = mask.map_or_else(LookAnglesComputation::default, LookAnglesComputation::from);
+        let compute = LookAnglesComputation {
+            nominal_frequency: req.nominal_frequency.map(Frequency::from),
+            atmosphere: req.atmosphere.map(TryInto::try_into).transpose()?,
+            equatorial_frame: EquatorialFrame::try_from(req.equatorial_frame)
+                .map_err(|_| Status::invalid_argument("Unknown equatorial frame"))?
+                .into(),
+            ..mask.map_or_else(LookAnglesComputation::default, LookAnglesComputation::from)
+        };
+
+        if (compute.downlink_frequency || compute.uplink_frequency)
+            && compute.nominal_frequency.is_none()
+        {
+            return Err(Status::invalid_argument(
+                "Nominal frequency is required when Doppler-shifted frequencies are requested",
+            ));
+        }
+
+        if compute
+            .nominal_frequency
+            .is_some_and(|f| f.get::<hertz>() <= 0.0 || !f.get::<hertz>().is_finite())
+        {
+            return Err(Status::invalid_argument(
+                "Nominal frequency must be positive",
+            ));
+        }
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
 
         let (look_angles, metadata) = self
             .look_angles_service
-            .get_look_angles_with_metadata(identifier, datetime, &observer, &compute)
+            .get_look_angles_with_metadata(identifier, datetime, observer, compute, options)
+            .await?;
+
+        let response =
+            LookAnglesResponse::from_look_angles(&look_angles, metadata, req.units, &clock)?;
+        Ok(Response::new(response))
+    }
+
+    async fn predict_passes(
+        &self,
+        request: Request<PredictPassesRequest>,
+    ) -> Result<Response<PredictPassesResponse>, Status> {
+        let req = request.into_inner();
+
+        let identifier = req
+            .identifier
+            .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
+            .try_into()?;
+
+        let observer = req
+            .observer
+            .ok_or_else(|| Status::invalid_argument("Missing observer"))?
+            .try_into()?;
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let start = clock.utc(
+            req.start
+                .ok_or_else(|| Status::invalid_argument("Missing start"))?
+                .to_chrono()?,
+        );
+
+        let end = clock.utc(
+            req.end
+                .ok_or_else(|| Status::invalid_argument("Missing end"))?
+                .to_chrono()?,
+        );
+
+        if end <= start {
+            return Err(Status::invalid_argument("End must be after start"));
+        }
+
+        if end - start > MAX_PASS_WINDOW {
+            return Err(Status::invalid_argument(format!(
+                "Window must not exceed {} days",
+                MAX_PASS_WINDOW.num_days()
+            )));
+        }
+
+        let min_elevation = req
+            .min_elevation
+            .map_or_else(|| Angle::new::<degree>(0.0), Angle::from);
+
+        if min_elevation.get::<degree>().abs() > 90.0 {
+            return Err(Status::invalid_argument(
+                "Minimum elevation must be within [-90°, 90°]",
+            ));
+        }
+
+        let max_sun_elevation = req.visibility.map(|visibility| {
+            visibility.max_sun_elevation.map_or_else(
+                || Angle::new::<degree>(DEFAULT_MAX_SUN_ELEVATION_DEG),
+                Angle::from,
+            )
+        });
+
+        if max_sun_elevation.is_some_and(|angle| angle.get::<degree>().abs() > 90.0) {
+            return Err(Status::invalid_argument(
+                "Maximum Sun elevation must be within [-90°, 90°]",
+            ));
+        }
+
+        let criteria = PassCriteria {
+            min_elevation,
+            max_sun_elevation,
+            atmosphere: req.atmosphere.map(TryInto::try_into).transpose()?,
+        };
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let (passes, metadata) = self
+            .passes_service
+            .predict_passes_with_metadata(identifier, observer, start, end, criteria, options)
+            .await?;
+
+        let response = PredictPassesResponse::from_passes(&passes, metadata, req.units, &clock)?;
+        Ok(Response::new(response))
+    }
+
+    async fn get_ephemeris(
+        &self,
+        request: Request<EphemerisRequest>,
+    ) -> Result<Response<EphemerisResponse>, Status> {
+        let req = request.into_inner();
+
+        let identifier = req
+            .identifier
+            .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
+            .try_into()?;
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetimes = ephemeris_datetimes(
+            req.times
+                .ok_or_else(|| Status::invalid_argument("Missing time range or timestamps"))?,
+            &clock,
+        )?;
+
+        let mask = req.output_mask.as_ref();
+
+        let compute = mask.map_or_else(PositionComputation::default, PositionComputation::from);
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let (positions, metadata) = self
+            .ephemeris_service
+            .get_ephemeris_with_metadata(identifier, &datetimes, compute, options)
+            .await?;
+
+        let response =
+            EphemerisResponse::from_samples(&datetimes, &positions, metadata, req.units, &clock)?;
+        Ok(Response::new(response))
+    }
+
+    async fn track_satellite(
+        &self,
+        request: Request<TrackRequest>,
+    ) -> Result<Response<Self::TrackSatelliteStream>, Status> {
+        let req = request.into_inner();
+
+        let identifier = req
+            .identifier
+            .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
+            .try_into()?;
+
+        let interval = req
+            .interval
+            .ok_or_else(|| Status::invalid_argument("Missing interval"))?
+            .to_time_delta()?;
+
+        if interval < MIN_TRACK_INTERVAL {
+            return Err(Status::invalid_argument(format!(
+                "Interval must be at least {} ms",
+                MIN_TRACK_INTERVAL.num_milliseconds()
+            )));
+        }
+
+        let interval = interval
+            .to_std()
+            .map_err(|_| Status::invalid_argument("Invalid interval"))?;
+
+        let observer = req.observer.map(TryInto::try_into).transpose()?;
+
+        let mask = req.output_mask.as_ref();
+
+        let position_compute =
+            mask.map_or_else(PositionComputation::default, PositionComputation::from);
+        let look_angles_compute =
+            mask.map_or_else(LookAnglesComputation::default, LookAnglesComputation::from);
+
+        let wants_look_angles = look_angles_compute.azimuth
+            || look_angles_compute.elevation
+            || look_angles_compute.range;
+
+        if wants_look_angles && observer.is_none() {
+            return Err(Status::invalid_argument(
+                "Observer is required when look angles are requested",
+            ));
+        }
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let receiver = self
+            .tracking_service
+            .track(
+                identifier,
+                observer,
+                interval,
+                position_compute,
+                look_angles_compute,
+                options,
+            )
             .await?;
 
-        let response = LookAnglesResponse::from_look_angles(&look_angles, metadata, req.units)?;
+        let units = req.units;
+        let scale = time_scale(units.as_ref())?;
+        let eop = self.eop.clone();
+        let stream = ReceiverStream::new(receiver).map(move |item| {
+            let (sample, metadata) = item?;
+            TrackResponse::from_sample(&sample, metadata, units, &Clock::new(scale, &eop))
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_positions(
+        &self,
+        request: Request<PositionsRequest>,
+    ) -> Result<Response<PositionsResponse>, Status> {
+        let req = request.into_inner();
+
+        let identifiers = match req
+            .selection
+            .ok_or_else(|| Status::invalid_argument("Missing satellite selection"))?
+        {
+            positions_request::Selection::All(true) => None,
+            positions_request::Selection::All(false) => {
+                return Err(Status::invalid_argument(
+                    "Either list satellites or set all to true",
+                ));
+            }
+            positions_request::Selection::Satellites(list) => Some(
+                list.identifiers
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetime = clock.utc(
+            req.datetime
+                .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
+                .to_chrono()?,
+        );
+
+        let mask = req.output_mask.as_ref();
+
+        let compute = mask.map_or_else(PositionComputation::default, PositionComputation::from);
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let outcomes = self
+            .position_service
+            .get_positions_with_metadata(identifiers, datetime, compute, options)
+            .await?;
+
+        let results = outcomes
+            .into_iter()
+            .map(|(identifier, outcome)| {
+                PositionResult::from_outcome(identifier, outcome, req.units, &clock)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Response::new(PositionsResponse { results }))
+    }
+
+    async fn get_eclipses(
+        &self,
+        request: Request<EclipsesRequest>,
+    ) -> Result<Response<EclipsesResponse>, Status> {
+        let req = request.into_inner();
+
+        let identifier = req
+            .identifier
+            .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
+            .try_into()?;
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let start = clock.utc(
+            req.start
+                .ok_or_else(|| Status::invalid_argument("Missing start"))?
+                .to_chrono()?,
+        );
+
+        let end = clock.utc(
+            req.end
+                .ok_or_else(|| Status::invalid_argument("Missing end"))?
+                .to_chrono()?,
+        );
+
+        if end <= start {
+            return Err(Status::invalid_argument("End must be after start"));
+        }
+
+        if end - start > MAX_ECLIPSE_WINDOW {
+            return Err(Status::invalid_argument(format!(
+                "Window must not exceed {} days",
+                MAX_ECLIPSE_WINDOW.num_days()
+            )));
+        }
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let (eclipses, metadata) = self
+            .eclipses_service
+            .get_eclipses_with_metadata(identifier, start, end, options)
+            .await?;
+
+        let response = EclipsesResponse::from_eclipses(&eclipses, metadata, req.units, &clock)?;
         Ok(Response::new(response))
     }
+
+    async fn get_celestial_body_position(
+        &self,
+        request: Request<CelestialBodyRequest>,
+    ) -> Result<Response<CelestialBodyResponse>, Status> {
+        let req = request.into_inner();
+
+        let body = CelestialBody::try_from(req.body)
+            .map_err(|_| Status::invalid_argument("Unknown celestial body"))?;
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetime = clock.utc(
+            req.datetime
+                .ok_or_else(|| Status::invalid_argument("Missing datetime"))?
+                .to_chrono()?,
+        );
+
+        let observer = req.observer.map(TryInto::try_into).transpose()?;
+
+        let mask = req.output_mask.as_ref();
+
+        let position_compute =
+            mask.map_or_else(PositionComputation::default, PositionComputation::from);
+        let look_angles_compute =
+            mask.map_or_else(LookAnglesComputation::default, LookAnglesComputation::from);
+
+        let wants_look_angles = look_angles_compute.azimuth
+            || look_angles_compute.elevation
+            || look_angles_compute.range;
+
+        if wants_look_angles && observer.is_none() {
+            return Err(Status::invalid_argument(
+                "Observer is required when look angles are requested",
+            ));
+        }
+
+        let position = celestial::CelestialBody::try_from(body)?.position_at(
+            datetime,
+            observer.as_ref(),
+            &position_compute,
+            &look_angles_compute,
+        );
+
+        let response =
+            CelestialBodyResponse::from_position(body, datetime, &position, req.units, &clock)?;
+        Ok(Response::new(response))
+    }
+
+    async fn get_orbital_elements(
+        &self,
+        request: Request<OrbitalElementsRequest>,
+    ) -> Result<Response<OrbitalElementsResponse>, Status> {
+        let req = request.into_inner();
+
+        let identifier = req
+            .identifier
+            .ok_or_else(|| Status::invalid_argument("Missing satellite identifier"))?
+            .try_into()?;
+
+        let clock = Clock::new(time_scale(req.units.as_ref())?, &self.eop);
+
+        let datetime = req
+            .datetime
+            .map(|timestamp| timestamp.to_chrono().map(|reading| clock.utc(reading)))
+            .transpose()?;
+
+        let options = propagation_options(req.propagator, req.tle_age_limits)?;
+
+        let (elements, metadata) = self
+            .orbital_elements_service
+            .get_orbital_elements_with_metadata(identifier, datetime, options)
+            .await?;
+
+        let response =
+            OrbitalElementsResponse::from_elements(&elements, metadata, req.units, &clock)?;
+        Ok(Response::new(response))
+    }
+
+    async fn convert_tle_to_omm(
+        &self,
+        request: Request<ConvertTleToOmmRequest>,
+    ) -> Result<Response<ConvertTleToOmmResponse>, Status> {
+        let req = request.into_inner();
+
+        let format = omm_format(req.format)?;
+
+        let element_set = ElementSet::try_from(
+            req.tle
+                .ok_or_else(|| Status::invalid_argument("Missing TLE"))?,
+        )?;
+
+        Ok(Response::new(ConvertTleToOmmResponse {
+            omm: element_set.to_omm(format, Utc::now()),
+        }))
+    }
+
+    async fn convert_omm_to_tle(
+        &self,
+        request: Request<ConvertOmmToTleRequest>,
+    ) -> Result<Response<ConvertOmmToTleResponse>, Status> {
+        let req = request.into_inner();
+
+        let format = omm_format(req.format)?;
+
+        let element_set = ElementSet::from_omm(&req.omm, format)?;
+
+        Ok(Response::new(ConvertOmmToTleResponse {
+            tle: Some(TleLines::try_from(&element_set)?),
+        }))
+    }
+
+    async fn upload_ephemeris(
+        &self,
+        request: Request<UploadEphemerisRequest>,
+    ) -> Result<Response<UploadEphemerisResponse>, Status> {
+        let req = request.into_inner();
+
+        let ephemeris_id = req.ephemeris_id.trim().to_string();
+        if ephemeris_id.is_empty() {
+            return Err(Status::invalid_argument("Missing ephemeris ID"));
+        }
+
+        let ephemeris = match req
+            .source
+            .ok_or_else(|| Status::invalid_argument("Missing ephemeris"))?
+        {
+            upload_ephemeris_request::Source::OemKvn(document) => Ephemeris::from_oem(&document)?,
+            upload_ephemeris_request::Source::States(table) => table.try_into()?,
+        };
+
+        let (start, stop) = ephemeris.coverage();
+        let state_count = u32::try_from(ephemeris.len()).unwrap_or(u32::MAX);
+        self.ephemeris_store
+            .insert(ephemeris_id.clone(), ephemeris)?;
+
+        Ok(Response::new(UploadEphemerisResponse {
+            ephemeris_id,
+            coverage_start: Some(start.to_proto_timestamp()?),
+            coverage_stop: Some(stop.to_proto_timestamp()?),
+            state_count,
+        }))
+    }
 }
@@ -0,0 +1,22 @@
+use actix_web::{HttpResponse, web};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::service::propagator_cache::{PropagatorCache, PropagatorCacheStats};
+use crate::transport::adapter::tle_cache::{TleCache, TleCacheStats};
+
+#[derive(Serialize)]
+struct CacheStatsResponse {
+    tle: TleCacheStats,
+    propagator: PropagatorCacheStats,
+}
+
+pub async fn cache_stats(
+    tle_cache: web::Data<Arc<TleCache>>,
+    propagator_cache: web::Data<Arc<PropagatorCache>>,
+) -> HttpResponse {
+    HttpResponse::Ok().json(CacheStatsResponse {
+        tle: tle_cache.stats(),
+        propagator: propagator_cache.stats(),
+    })
+}
@@ -1,7 +1,10 @@
 use actix_web::web;
 
+use crate::transport::http::handlers::cache::cache_stats;
 use crate::transport::http::handlers::health::health_check;
 
 pub fn configure(config: &mut web::ServiceConfig) {
-    config.service(web::resource("/health").to(health_check));
+    config
+        .service(web::resource("/health").to(health_check))
+        .service(web::resource("/cache/stats").route(web::get().to(cache_stats)));
 }
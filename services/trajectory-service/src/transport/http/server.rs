@@ -1,17 +1,36 @@
-use actix_web::{App, HttpServer, dev::Server};
+use actix_web::{App, HttpServer, dev::Server, web};
 use actix_web_prom::PrometheusMetricsBuilder;
+use std::sync::Arc;
 
 use crate::domain::errors::HttpServerError;
+use crate::service::propagator_cache::PropagatorCache;
+use crate::transport::adapter::tle_cache::TleCache;
 use crate::transport::http;
 
-pub fn run(port: u16) -> Result<Server, HttpServerError> {
+pub fn run(
+    port: u16,
+    tle_cache: Arc<TleCache>,
+    propagator_cache: Arc<PropagatorCache>,
+) -> Result<Server, HttpServerError> {
     let prometheus = PrometheusMetricsBuilder::new("api")
         .endpoint("/metrics")
         .build()
         .map_err(|e| HttpServerError::Prometheus(e.to_string()))?;
 
+    tle_cache
+        .register(&prometheus.registry)
+        .map_err(|e| HttpServerError::Prometheus(e.to_string()))?;
+    propagator_cache
+        .register(&prometheus.registry)
+        .map_err(|e| HttpServerError::Prometheus(e.to_string()))?;
+
+    let tle_cache = web::Data::new(tle_cache);
+    let propagator_cache = web::Data::new(propagator_cache);
+
     let server = HttpServer::new(move || {
         App::new()
+            .app_data(tle_cache.clone())
+            .app_data(propagator_cache.clone())
             .wrap(prometheus.clone())
             .wrap(actix_web::middleware::Logger::default())
             .configure(http::routes::configure)